@@ -0,0 +1,619 @@
+use std::time::Duration;
+
+use avian2d::prelude::*;
+use bevy::{color::palettes::basic::RED, ecs::system::SystemParam, prelude::*};
+use pong_core::{
+    paddle_bounce, paddles_can_move, store_match_geometry, Ai, AiController, AiSettings, AiView,
+    Arena, Ball, BallHit, BallSample, GameConfig, GameMode, GameRng, GameState, HitKind, InGame,
+    MatchGeometry, Paddle, Player, PlayerActions, PlayerSlot, PongPlugin, Scored, Scorer, Server,
+    MAX_SERVE_ANGLE, MIN_SERVE_ANGLE, STALL_TIME,
+};
+use rand::Rng;
+
+// Valores padrão do `GameConfig` desta versão; o jogo usa sempre os da configuração
+pub const BALL_RADIUS: f32 = 10.0;
+pub const BALL_SPEED: f32 = 500.0; // Velocidade inicial da bola a cada saque
+pub const SPEED_INCREMENT: f32 = 10.0; // Aumento da velocidade a cada toque de raquete
+pub const WALL_THICKNESS: f32 = 100.0;
+pub const PADDLE_WIDTH: f32 = 15.0; // Largura das raquetes
+pub const PADDLE_HEIGHT: f32 = 75.0; // Altura das raquetes
+pub const PADDLE_SPEED: f32 = 600.0; // Velocidade das raquetes
+pub const MAX_PITCH: f32 = 2.0; // Tom dos toques na velocidade máxima da bola (uma oitava acima)
+
+#[derive(Component, Clone, Debug)]
+pub struct BallMovement {
+    pub angle: f32,
+    pub speed: f32,
+    pub hits: u32, // Toques que já aceleraram a bola, usados pela curva de velocidade
+}
+
+// Texto com a velocidade atual da bola
+#[derive(Component)]
+pub struct SpeedText;
+
+#[derive(Component)]
+pub struct Wall;
+
+// Recurso com o tempo desde o último toque de raquete; ao chegar a `stall_time` da
+// configuração, o rali é dado como parado
+#[derive(Resource, Debug)]
+pub struct StallTimer(pub Timer);
+
+impl Default for StallTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(STALL_TIME, TimerMode::Once))
+    }
+}
+
+// Sensor atrás de cada raquete; guarda quem marca ponto quando a bola o atinge
+#[derive(Component)]
+pub struct Goal(pub Scorer);
+
+pub fn game_config() -> GameConfig {
+    GameConfig {
+        ball_radius: BALL_RADIUS,
+        ball_color: Color::from(RED),
+        ball_speed: BALL_SPEED,
+        speed_increment: SPEED_INCREMENT,
+        paddle_speed: PADDLE_SPEED,
+        wall_thickness: WALL_THICKNESS,
+        paddle_width: PADDLE_WIDTH,
+        paddle_height: PADDLE_HEIGHT,
+        ..default()
+    }
+}
+
+// Ângulo do saque em graus, de 15° a 45° acima ou abaixo da horizontal como no pong simples;
+// o lado é escolhido no saque
+pub fn get_random_ball_start_angle(rng: &mut impl Rng) -> f32 {
+    let angle = rng
+        .random_range(MIN_SERVE_ANGLE..=MAX_SERVE_ANGLE)
+        .to_degrees();
+    if rng.random_bool(0.5) {
+        angle
+    } else {
+        -angle
+    }
+}
+
+// Velocidade da bola com pelo menos `min_horizontal_speed` na horizontal e no máximo
+// `max_ball_angle` de inclinação, mantendo o módulo e os sentidos. Evita que reflexões deixem a
+// bola quicando entre as paredes sem chegar às raquetes
+pub fn keep_rally_moving(velocity: Vec2, config: &GameConfig) -> Vec2 {
+    let speed = velocity.length();
+    if speed == 0.0 {
+        return velocity;
+    }
+    let min_cos = config
+        .max_ball_angle
+        .cos()
+        .max((config.min_horizontal_speed / speed).min(1.0));
+    let max_angle = min_cos.acos();
+    let angle = velocity
+        .y
+        .atan2(velocity.x.abs())
+        .clamp(-max_angle, max_angle);
+    Vec2::new(velocity.x.signum() * angle.cos(), angle.sin()) * speed
+}
+
+// Tom do som de um toque: normal na velocidade do saque, subindo até `MAX_PITCH` na velocidade
+// máxima da bola
+pub fn hit_pitch(speed: f32, config: &GameConfig) -> f32 {
+    let progress = (speed - config.ball_speed) / (config.max_ball_speed - config.ball_speed);
+    1.0 + progress.clamp(0.0, 1.0) * (MAX_PITCH - 1.0)
+}
+
+pub fn velocity_from_angle(angle_degrees: f32, speed: f32) -> Vec2 {
+    // Convert angle from degrees to radians
+    let angle_radians = angle_degrees.to_radians();
+
+    // Calculate direction vector components
+    let x = speed * angle_radians.cos();
+    let y = speed * angle_radians.sin();
+
+    // Return the velocity vector
+    Vec2::new(x, y)
+}
+
+pub fn spawn_play_field(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    arena: Res<Arena>,
+    geometry: Res<MatchGeometry>,
+) {
+    let arena_width = arena.width();
+    let arena_height = arena.height();
+    let wall_thickness = geometry.wall_thickness;
+
+    // Top wall
+    commands.spawn((
+        Position::from_xy(0.0, (arena_height - wall_thickness) / 2.0),
+        Mesh2d(meshes.add(Rectangle::from_size(Vec2::new(arena_width, wall_thickness)))),
+        MeshMaterial2d(materials.add(Color::srgb(0., 0., 0.))),
+        RigidBody::Static,
+        Collider::rectangle(arena_width, wall_thickness),
+        Wall,
+        StateScoped(InGame),
+    ));
+
+    // Bottom wall
+    commands.spawn((
+        Position::from_xy(0.0, -((arena_height - wall_thickness) / 2.0)),
+        Mesh2d(meshes.add(Rectangle::from_size(Vec2::new(arena_width, wall_thickness)))),
+        MeshMaterial2d(materials.add(Color::srgb(0., 0., 0.))),
+        RigidBody::Static,
+        Collider::rectangle(arena_width, wall_thickness),
+        Wall,
+        StateScoped(InGame),
+    ));
+
+    // Left goal - positioned just outside the visible area, the right player scores here
+    commands.spawn((
+        Position::from_xy(-(arena_width / 2.0 + wall_thickness / 2.0), 0.0),
+        RigidBody::Static,
+        Collider::rectangle(wall_thickness, arena_height),
+        Sensor,
+        Goal(Scorer::Right),
+        StateScoped(InGame),
+    ));
+
+    // Right goal - positioned just outside the visible area, the left player scores here
+    commands.spawn((
+        Position::from_xy(arena_width / 2.0 + wall_thickness / 2.0, 0.0),
+        RigidBody::Static,
+        Collider::rectangle(wall_thickness, arena_height),
+        Sensor,
+        Goal(Scorer::Left),
+        StateScoped(InGame),
+    ));
+}
+
+// Adiciona a física do avian à bola criada pelo `PongPlugin`
+pub fn add_ball_physics(
+    trigger: Trigger<OnAdd, Ball>,
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    geometry: Res<MatchGeometry>,
+    mut rng: ResMut<GameRng>,
+) {
+    let initial_angle = get_random_ball_start_angle(&mut *rng);
+    let speed = config.ball_speed;
+    commands.entity(trigger.entity()).insert((
+        RigidBody::Dynamic,
+        TransformInterpolation, // Movimento suave entre os passos fixos da física
+        SweptCcd::LINEAR, // Varre o trajeto do passo para não atravessar as raquetes em alta velocidade
+        Collider::circle(geometry.ball_radius),
+        Restitution::PERFECTLY_ELASTIC,
+        Friction::ZERO,
+        LinearDamping(0.0),
+        Mass::ZERO,
+        // A bola fica parada até o saque
+        LinearVelocity::ZERO,
+        // Agora adiciona o componente que armazena o estado do movimento
+        BallMovement {
+            angle: initial_angle,
+            speed,
+            hits: 0,
+        },
+    ));
+}
+
+// Cria as duas raquetes: a da direita usa as setas e a da esquerda usa W/S
+pub fn spawn_paddles(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    arena: Res<Arena>,
+    geometry: Res<MatchGeometry>,
+    mode: Res<GameMode>,
+) {
+    let paddle_x = arena.width() / 2.0 - geometry.wall_thickness / 2.0;
+    let mesh = meshes.add(Rectangle::from_size(geometry.paddle_size));
+    let material = materials.add(Color::srgb(255.0, 255.0, 255.0));
+
+    let paddle = || {
+        (
+            Mesh2d(mesh.clone()),
+            MeshMaterial2d(material.clone()),
+            RigidBody::Kinematic,
+            TransformInterpolation,
+            Collider::rectangle(geometry.paddle_size.x, geometry.paddle_size.y),
+            Restitution::PERFECTLY_ELASTIC,
+            Friction::ZERO,
+            Paddle,
+            StateScoped(InGame),
+        )
+    };
+
+    commands.spawn((
+        Position::from_xy(paddle_x, 0.0),
+        PlayerSlot(0),
+        Player,
+        paddle(),
+    ));
+
+    let left = commands
+        .spawn((Position::from_xy(-paddle_x, 0.0), paddle()))
+        .id();
+    match *mode {
+        GameMode::SinglePlayer => commands.entity(left).insert(Ai),
+        GameMode::TwoPlayers => commands.entity(left).insert(PlayerSlot(1)),
+    };
+}
+
+// Move as raquetes conforme as ações de cada jogador, sem deixar que atravessem as paredes
+pub fn handle_paddle_input(
+    actions: Res<PlayerActions>,
+    mut paddles: Query<(&PlayerSlot, &mut LinearVelocity, &mut Position), With<Paddle>>,
+    arena: Res<Arena>,
+    geometry: Res<MatchGeometry>,
+    config: Res<GameConfig>,
+) {
+    let limit = geometry.paddle_limit(&arena);
+
+    for (slot, mut velocity, mut position) in &mut paddles {
+        // Teclas e botões valem -1 ou 1; os eixos analógicos dão valores intermediários
+        let mut direction = actions.get(slot.0).movement;
+
+        // Para a raquete ao alcançar uma das paredes
+        position.y = position.y.clamp(-limit, limit);
+        if (position.y >= limit && direction > 0.0) || (position.y <= -limit && direction < 0.0) {
+            direction = 0.0;
+        }
+
+        velocity.0 = Vec2::new(0.0, direction * config.paddle_speed);
+    }
+}
+
+// Move as raquetes da IA com a mesma velocidade e os mesmos limites das raquetes dos jogadores
+pub fn move_ai_paddles(
+    mut paddles: Query<(&mut AiController, &mut LinearVelocity, &mut Position), Without<Ball>>,
+    ball: Query<(&Position, &LinearVelocity), With<Ball>>,
+    settings: Res<AiSettings>,
+    (arena, geometry): (Res<Arena>, Res<MatchGeometry>), // Limites da partida
+    config: Res<GameConfig>,
+    time: Res<Time>,
+    mut rng: ResMut<GameRng>,
+) {
+    let Ok((ball_position, ball_velocity)) = ball.get_single() else {
+        return;
+    };
+    let limit = geometry.paddle_limit(&arena);
+    // Faixa em que o centro da bola anda entre as paredes
+    let edge = geometry.ball_limit(&arena);
+
+    for (mut controller, mut velocity, mut position) in &mut paddles {
+        let view = AiView {
+            paddle: position.0,
+            ball: ball_position.0,
+            ball_velocity: ball_velocity.0,
+            floor: -edge,
+            ceiling: edge,
+        };
+        let mut direction = controller.steer(&settings, &view, time.delta_secs(), &mut *rng);
+
+        // Para a raquete ao alcançar uma das paredes
+        position.y = position.y.clamp(-limit, limit);
+        if (position.y >= limit && direction > 0.0) || (position.y <= -limit && direction < 0.0) {
+            direction = 0.0;
+        }
+        velocity.0 = Vec2::new(0.0, direction * config.paddle_speed);
+    }
+}
+
+// Com o que a bola se encontrou em um contato, e o que é preciso para responder a ele
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BallContact {
+    Wall, // Parede: reflete a bola pela normal do contato
+    // Raquete: rebatida com ângulo pelo ponto de contato e efeito pelo movimento da raquete
+    Paddle { position: Vec2, velocity: Vec2 },
+    Goal(Scorer), // Gol: ponto para quem está indicado
+}
+
+// Raquetes, separadas da bola para consultar as duas velocidades ao mesmo tempo
+type PaddleOnly = (With<Paddle>, Without<Ball>);
+
+// Parâmetro de sistema que classifica o corpo tocado pela bola
+#[derive(SystemParam)]
+pub struct ContactClassifier<'w, 's> {
+    walls: Query<'w, 's, (), With<Wall>>,
+    paddles: Query<'w, 's, (&'static Position, &'static LinearVelocity), PaddleOnly>,
+    goals: Query<'w, 's, &'static Goal>,
+}
+
+impl ContactClassifier<'_, '_> {
+    // Tipo de contato com a entidade, ou `None` se ela não afeta a bola
+    pub fn classify(&self, entity: Entity) -> Option<BallContact> {
+        if self.walls.contains(entity) {
+            Some(BallContact::Wall)
+        } else if let Ok((position, velocity)) = self.paddles.get(entity) {
+            Some(BallContact::Paddle {
+                position: position.0,
+                velocity: velocity.0,
+            })
+        } else {
+            self.goals
+                .get(entity)
+                .ok()
+                .map(|goal| BallContact::Goal(goal.0))
+        }
+    }
+}
+
+// Sistema que responde aos contatos da bola: paredes refletem, raquetes rebatem com ângulo,
+// efeito e aceleração, e gols enviam `Scored`
+pub fn collision_system(
+    mut collision_events: EventReader<Collision>,
+    mut ball_query: Query<(&mut LinearVelocity, &mut BallMovement, &Position), With<Ball>>,
+    classifier: ContactClassifier,
+    config: Res<GameConfig>,
+    geometry: Res<MatchGeometry>,
+    mut hits: EventWriter<BallHit>,
+    mut scored: EventWriter<Scored>,
+) {
+    for Collision(contacts) in collision_events.read() {
+        if !contacts.collision_started() {
+            continue;
+        }
+
+        let (ball_entity, other) = if ball_query.contains(contacts.entity1) {
+            (contacts.entity1, contacts.entity2)
+        } else if ball_query.contains(contacts.entity2) {
+            (contacts.entity2, contacts.entity1)
+        } else {
+            continue;
+        };
+        let Some(contact) = classifier.classify(other) else {
+            continue;
+        };
+        let Ok((mut velocity, mut ball_movement, ball_position)) = ball_query.get_mut(ball_entity)
+        else {
+            continue;
+        };
+
+        if let BallContact::Goal(scorer) = contact {
+            scored.send(Scored(scorer));
+            continue;
+        }
+        let Some(manifold) = contacts.manifolds.first() else {
+            continue;
+        };
+        let normal = manifold.global_normal2(&Rotation::default());
+
+        let old_velocity = velocity.0;
+        let reflected = old_velocity - 2.0 * old_velocity.dot(normal) * normal;
+        let (kind, new_velocity) = match contact {
+            // Na face de uma raquete o ângulo de saída depende do ponto de contato e do
+            // movimento da raquete; nas pontas ela reflete como uma parede
+            BallContact::Paddle { position, velocity } if normal.x.abs() > normal.y.abs() => (
+                HitKind::Paddle,
+                paddle_bounce(
+                    old_velocity,
+                    -position.x.signum(), // Volta para o centro da arena
+                    ball_position.y - position.y,
+                    geometry.paddle_size.y / 2.0,
+                    velocity.y,
+                    &config,
+                ),
+            ),
+            BallContact::Paddle { .. } => (HitKind::Paddle, reflected),
+            _ => (HitKind::Wall, reflected),
+        };
+
+        // Só as raquetes aceleram, pela curva da configuração e sem passar do limite em que a
+        // colisão contínua é garantida; as paredes só refletem
+        if kind == HitKind::Paddle {
+            ball_movement.speed = config.next_ball_speed(ball_movement.speed, ball_movement.hits);
+            ball_movement.hits += 1;
+        }
+
+        // Mantém a direção, sem deixar a bola quase vertical, e aplica a nova velocidade
+        let new_velocity =
+            keep_rally_moving(new_velocity.normalize() * ball_movement.speed, &config);
+        velocity.0 = new_velocity;
+        ball_movement.angle = new_velocity.y.atan2(new_velocity.x).to_degrees();
+
+        // Quanto mais rápida a bola, mais agudo o som do toque
+        hits.send(BallHit {
+            kind,
+            position: ball_position.0,
+            pitch: hit_pitch(ball_movement.speed, &config),
+        });
+
+        debug!(
+            "Reflexão: velocidade alterada de {:?} para {:?}, nova velocidade: {}",
+            old_velocity, new_velocity, ball_movement.speed
+        );
+    }
+}
+
+// Recoloca a bola parada no centro após cada ponto, com um novo ângulo guardado para o próximo
+// saque. A velocidade volta à inicial, a não ser que `reset_speed_on_score` esteja desligado
+pub fn reset_ball(
+    mut events: EventReader<Scored>,
+    mut ball_query: Query<(&mut Position, &mut LinearVelocity, &mut BallMovement), With<Ball>>,
+    config: Res<GameConfig>,
+    mut rng: ResMut<GameRng>,
+) {
+    for _ in events.read() {
+        if let Ok((mut position, mut velocity, mut ball_movement)) = ball_query.get_single_mut() {
+            position.0 = Vec2::ZERO;
+            velocity.0 = Vec2::ZERO;
+            ball_movement.angle = get_random_ball_start_angle(&mut *rng);
+            if config.reset_speed_on_score {
+                ball_movement.speed = config.ball_speed;
+                ball_movement.hits = 0;
+            }
+        }
+    }
+}
+
+// Sistema que saca a bola de novo, sem ponto, quando passam `stall_time` segundos sem que ela
+// toque uma raquete. A bola volta ao centro com a velocidade inicial e o mesmo sacador
+pub fn detect_stalled_rally(
+    mut hits: EventReader<BallHit>,
+    mut timer: ResMut<StallTimer>,
+    mut ball_query: Query<(&mut Position, &mut LinearVelocity, &mut BallMovement), With<Ball>>,
+    config: Res<GameConfig>,
+    time: Res<Time>,
+    mut rng: ResMut<GameRng>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if hits.read().any(|hit| hit.kind == HitKind::Paddle) {
+        timer.0.reset();
+    }
+    // Acompanha a configuração, que pode ser recarregada no meio do rali
+    timer
+        .0
+        .set_duration(Duration::from_secs_f32(config.stall_time));
+    if !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    if let Ok((mut position, mut velocity, mut ball_movement)) = ball_query.get_single_mut() {
+        info!(
+            "Rali parado por {} segundos; sacando de novo",
+            config.stall_time
+        );
+        position.0 = Vec2::ZERO;
+        velocity.0 = Vec2::ZERO;
+        *ball_movement = BallMovement {
+            angle: get_random_ball_start_angle(&mut *rng),
+            speed: config.ball_speed,
+            hits: 0,
+        };
+        next_state.set(GameState::Serving);
+    }
+}
+
+// Zera o tempo do rali a cada saque
+pub fn reset_stall_timer(mut timer: ResMut<StallTimer>) {
+    timer.0.reset();
+}
+
+// Saca a bola com o ângulo e a velocidade guardados em `BallMovement`, espelhando o ângulo
+// se preciso para que ela vá em direção ao adversário de quem saca
+pub fn launch_ball(
+    mut ball_query: Query<(&mut LinearVelocity, &mut BallMovement), With<Ball>>,
+    server: Res<Server>,
+) {
+    if let Ok((mut velocity, mut ball_movement)) = ball_query.get_single_mut() {
+        let mut launch = velocity_from_angle(ball_movement.angle, ball_movement.speed);
+        launch.x = launch.x.abs() * server.0.serve_direction();
+        ball_movement.angle = launch.y.atan2(launch.x).to_degrees();
+        velocity.0 = launch;
+    }
+}
+
+// Cria o texto com a velocidade da bola, centralizado embaixo da arena
+pub fn spawn_speed_display(mut commands: Commands) {
+    commands.spawn((
+        SpeedText,
+        Text::new(""),
+        TextFont {
+            font_size: 24.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(5.0),
+            width: Val::Percent(100.0),
+            ..default()
+        },
+        StateScoped(InGame),
+    ));
+}
+
+// Mostra a velocidade atual da bola, guardada em `BallMovement`
+pub fn update_speed_display(
+    ball: Query<&BallMovement, (With<Ball>, Changed<BallMovement>)>,
+    mut text: Query<&mut Text, With<SpeedText>>,
+) {
+    if let (Ok(movement), Ok(mut text)) = (ball.get_single(), text.get_single_mut()) {
+        text.0 = format!("Velocidade: {:.0}", movement.speed);
+    }
+}
+
+// Congela a física do avian durante a pausa
+pub fn pause_physics(mut time: ResMut<Time<Physics>>) {
+    time.pause();
+}
+
+// Retoma a física do avian ao sair da pausa
+pub fn resume_physics(mut time: ResMut<Time<Physics>>) {
+    time.unpause();
+}
+
+// Sistema que copia a bola para o `BallSample` depois da física de cada passo fixo
+pub fn sample_ball(
+    ball: Query<(&Position, &LinearVelocity), With<Ball>>,
+    mut sample: ResMut<BallSample>,
+) {
+    if let Ok((position, velocity)) = ball.get_single() {
+        *sample = BallSample {
+            position: position.0,
+            velocity: velocity.0,
+        };
+    }
+}
+
+// Regras do accelepong: física com avian, duas raquetes, gols e aceleração da bola a cada toque de raquete.
+// Funciona tanto com o `DefaultPlugins` quanto com o `HeadlessPlugin`.
+pub struct AccelepongPlugin;
+
+impl Plugin for AccelepongPlugin {
+    fn build(&self, app: &mut App) {
+        // Por padrão o accelepong é para dois jogadores no mesmo teclado
+        if !app.world().contains_resource::<GameMode>() {
+            app.insert_resource(GameMode::TwoPlayers);
+        }
+
+        // Mantém a configuração lida do arquivo pelo binário, se houver
+        if !app.world().contains_resource::<GameConfig>() {
+            app.insert_resource(game_config());
+        }
+
+        app.add_plugins((PongPlugin, PhysicsPlugins::default()))
+            .insert_resource(Gravity::ZERO)
+            .init_resource::<StallTimer>()
+            .add_observer(add_ball_physics)
+            .add_systems(
+                OnEnter(InGame),
+                (spawn_play_field, spawn_paddles, spawn_speed_display).after(store_match_geometry), // Com as medidas fixadas para a partida
+            )
+            .add_systems(Update, update_speed_display)
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Serving,
+                    entered: GameState::Playing,
+                },
+                (launch_ball, reset_stall_timer),
+            )
+            .add_systems(OnEnter(GameState::Paused), pause_physics)
+            .add_systems(OnExit(GameState::Paused), resume_physics)
+            // Entrada e colisões rodam no mesmo passo fixo da física do avian
+            .add_systems(
+                FixedUpdate,
+                (handle_paddle_input, move_ai_paddles).run_if(paddles_can_move),
+            )
+            .add_systems(
+                FixedPostUpdate,
+                (
+                    collision_system,
+                    reset_ball.after(collision_system),
+                    detect_stalled_rally
+                        .after(collision_system)
+                        .run_if(in_state(GameState::Playing)),
+                    // Bola vista pela gravação e pelos espectadores
+                    sample_ball
+                        .after(collision_system)
+                        .after(reset_ball)
+                        .after(detect_stalled_rally),
+                )
+                    .after(PhysicsSet::StepSimulation),
+            );
+    }
+}
@@ -0,0 +1,528 @@
+use accelepong::{
+    game_config, get_random_ball_start_angle, hit_pitch, keep_rally_moving, velocity_from_angle,
+    AccelepongPlugin, BallMovement, SpeedText, BALL_SPEED, MAX_PITCH, PADDLE_HEIGHT,
+    SPEED_INCREMENT, WALL_THICKNESS,
+};
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use pong_core::{
+    step, AiSettings, Arena, Ball, BallHit, Difficulty, GameConfig, GameMode, GameRng, GameState,
+    HeadlessPlugin, HitKind, MatchGeometry, Player, Score, SpeedCurve, MAX_BALL_ANGLE,
+    MIN_HORIZONTAL_SPEED, STALL_TIME,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+// Cria o jogo sem janela e executa os passos necessários para o avian preparar os corpos
+fn headless_app() -> App {
+    headless_app_with(|_| {})
+}
+
+// Como `headless_app`, permitindo inserir recursos antes dos plugins do jogo
+fn headless_app_with(configure: impl FnOnce(&mut App)) -> App {
+    let mut app = App::new();
+    // Começa jogando, sem passar pelo menu e pelo saque
+    app.add_plugins(HeadlessPlugin::default())
+        .insert_state(GameState::Playing);
+    configure(&mut app);
+    app.add_plugins(AccelepongPlugin);
+    step(&mut app, 2);
+    app
+}
+
+// Lança a bola do centro com o ângulo indicado
+fn launch_ball(app: &mut App, angle: f32) {
+    launch_ball_from(app, Vec2::ZERO, angle);
+}
+
+// Lança a bola da posição e com o ângulo indicados
+fn launch_ball_from(app: &mut App, start: Vec2, angle: f32) {
+    let mut ball = app
+        .world_mut()
+        .query_filtered::<(&mut Position, &mut LinearVelocity, &mut BallMovement), With<Ball>>();
+    let (mut position, mut velocity, mut movement) = ball.single_mut(app.world_mut());
+    position.0 = start;
+    velocity.0 = velocity_from_angle(angle, movement.speed);
+    movement.angle = angle;
+}
+
+// Aperta e solta uma tecla, como em um quadro real
+fn tap(app: &mut App, key: KeyCode) {
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(key);
+    step(app, 1);
+    let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keyboard.release(key);
+    keyboard.clear();
+    step(app, 1); // Aplica a troca de estado
+}
+
+fn ball_state(app: &mut App) -> (Vec2, BallMovement) {
+    let mut ball = app
+        .world_mut()
+        .query_filtered::<(&LinearVelocity, &BallMovement), With<Ball>>();
+    let (velocity, movement) = ball.single(app.world());
+    (velocity.0, movement.clone())
+}
+
+// Toques da bola enviados desde o início do teste
+#[derive(Resource, Default)]
+struct Hits(Vec<BallHit>);
+
+fn collect_hits(mut events: EventReader<BallHit>, mut hits: ResMut<Hits>) {
+    hits.0.extend(events.read().copied());
+}
+
+// Como `headless_app`, guardando os toques da bola em `Hits`
+fn app_collecting_hits() -> App {
+    headless_app_with(|app| {
+        app.init_resource::<Hits>()
+            .add_systems(Update, collect_hits);
+    })
+}
+
+#[test]
+fn ball_keeps_speed_without_contacts() {
+    let mut app = headless_app();
+    launch_ball(&mut app, 80.);
+
+    step(&mut app, 10);
+
+    let (velocity, movement) = ball_state(&mut app);
+    assert_eq!(movement.speed, 500.);
+    assert!((velocity.length() - 500.).abs() < 0.1);
+}
+
+#[test]
+fn wall_bounce_keeps_the_speed() {
+    let mut app = headless_app();
+    launch_ball(&mut app, 90.);
+
+    // Tempo suficiente para bater na parede superior e depois na inferior
+    step(&mut app, 200);
+
+    let (velocity, movement) = ball_state(&mut app);
+    assert!(
+        velocity.y > 0.,
+        "a bola deveria subir após a parede inferior"
+    );
+    assert_eq!((movement.speed, movement.hits), (BALL_SPEED, 0));
+    assert!((velocity.length() - BALL_SPEED).abs() < 0.1);
+}
+
+#[test]
+fn paddle_hit_increases_speed_by_increment() {
+    let mut app = headless_app();
+    launch_ball(&mut app, 0.);
+
+    // A bola vai em linha reta até a raquete da direita
+    step(&mut app, 130);
+
+    let (velocity, movement) = ball_state(&mut app);
+    assert!(velocity.x < 0., "a bola deveria voltar após a raquete");
+    assert_eq!(movement.speed, BALL_SPEED + SPEED_INCREMENT);
+    assert_eq!(movement.hits, 1);
+    assert!((velocity.length() - movement.speed).abs() < 0.1);
+}
+
+#[test]
+fn serve_speed_and_increment_come_from_the_config() {
+    let mut app = headless_app_with(|app| {
+        app.insert_resource(GameConfig {
+            ball_speed: 400.,
+            speed_increment: 25.,
+            ..game_config()
+        });
+    });
+    let (_, movement) = ball_state(&mut app);
+    assert_eq!(movement.speed, 400.);
+    launch_ball(&mut app, 0.);
+
+    step(&mut app, 160);
+
+    let (_, movement) = ball_state(&mut app);
+    assert_eq!(movement.speed, 425.);
+}
+
+#[test]
+fn speed_curve_comes_from_the_config() {
+    let mut app = headless_app_with(|app| {
+        app.insert_resource(GameConfig {
+            speed_curve: SpeedCurve::Multiplicative(1.1),
+            ..game_config()
+        });
+    });
+    launch_ball(&mut app, 0.);
+
+    // Raquete da direita, raquete da esquerda
+    step(&mut app, 400);
+
+    let (velocity, movement) = ball_state(&mut app);
+    assert!((movement.speed - 500. * 1.1 * 1.1).abs() < 0.01);
+    assert!((velocity.length() - movement.speed).abs() < 0.1);
+}
+
+#[test]
+fn hits_get_higher_as_the_ball_speeds_up() {
+    let mut app = app_collecting_hits();
+    launch_ball(&mut app, 0.);
+
+    // Raquete da direita, raquete da esquerda
+    step(&mut app, 400);
+
+    let hits = &app.world().resource::<Hits>().0;
+    assert_eq!(hits.len(), 2);
+    assert!(hits.iter().all(|hit| hit.kind == HitKind::Paddle));
+    assert!(hits[0].pitch > 1.);
+    assert!(hits[1].pitch > hits[0].pitch);
+}
+
+#[test]
+fn default_config_is_valid() {
+    assert_eq!(game_config().validate(), Ok(()));
+}
+
+#[test]
+fn hit_pitch_goes_from_normal_to_max_pitch() {
+    let config = game_config();
+
+    assert_eq!(hit_pitch(BALL_SPEED, &config), 1.);
+    assert_eq!(hit_pitch(config.max_ball_speed, &config), MAX_PITCH);
+    assert_eq!(hit_pitch(config.max_ball_speed * 2., &config), MAX_PITCH);
+}
+
+#[test]
+fn paddle_contact_sends_a_paddle_hit() {
+    let mut app = app_collecting_hits();
+    launch_ball(&mut app, 0.);
+
+    step(&mut app, 130);
+
+    let hits = &app.world().resource::<Hits>().0;
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].kind, HitKind::Paddle);
+}
+
+#[test]
+fn ball_entering_goal_scores_and_resets_speed() {
+    let mut app = headless_app();
+    // Acima da raquete da direita, direto para o gol
+    launch_ball_from(&mut app, Vec2::new(0., 200.), 0.);
+
+    step(&mut app, 160);
+
+    let score = app.world().resource::<Score>();
+    assert_eq!((score.left, score.right), (1, 0));
+    // A bola espera parada pelo saque
+    let state = app.world().resource::<State<GameState>>();
+    assert_eq!(*state.get(), GameState::Serving);
+    let (velocity, movement) = ball_state(&mut app);
+    assert_eq!(movement.speed, BALL_SPEED);
+    assert_eq!(velocity, Vec2::ZERO);
+
+    tap(&mut app, KeyCode::Space);
+
+    let (velocity, _) = ball_state(&mut app);
+    assert!((velocity.length() - BALL_SPEED).abs() < 0.1);
+}
+
+#[test]
+fn goal_contact_scores_once_without_a_hit() {
+    let mut app = app_collecting_hits();
+    // Acima da raquete da direita, direto para o gol
+    launch_ball_from(&mut app, Vec2::new(0., 200.), 0.);
+
+    step(&mut app, 160);
+
+    let score = app.world().resource::<Score>();
+    assert_eq!((score.left, score.right), (1, 0));
+    assert!(app.world().resource::<Hits>().0.is_empty());
+}
+
+#[test]
+fn speed_can_carry_over_to_the_next_point() {
+    let mut app = headless_app_with(|app| {
+        app.insert_resource(GameConfig {
+            reset_speed_on_score: false,
+            ..game_config()
+        });
+    });
+    launch_ball(&mut app, 0.);
+    step(&mut app, 130); // Acelera na raquete da direita
+                         // Acima da raquete da direita, direto para o gol
+    launch_ball_from(&mut app, Vec2::new(0., 200.), 0.);
+    step(&mut app, 160);
+
+    let score = app.world().resource::<Score>();
+    assert_eq!((score.left, score.right), (1, 0));
+    let (velocity, movement) = ball_state(&mut app);
+    assert_eq!(velocity, Vec2::ZERO);
+    assert_eq!(movement.speed, BALL_SPEED + SPEED_INCREMENT);
+}
+
+#[test]
+fn current_speed_is_shown_on_screen() {
+    let mut app = headless_app();
+    launch_ball(&mut app, 0.);
+    step(&mut app, 130);
+
+    let mut text = app.world_mut().query_filtered::<&Text, With<SpeedText>>();
+    let text = text.single(app.world());
+    assert_eq!(
+        text.0,
+        format!("Velocidade: {}", BALL_SPEED + SPEED_INCREMENT)
+    );
+}
+
+#[test]
+fn ball_bounces_off_paddle() {
+    let mut app = headless_app();
+    launch_ball(&mut app, 0.);
+
+    step(&mut app, 130);
+
+    let (velocity, _) = ball_state(&mut app);
+    assert!(velocity.x < 0., "a bola deveria voltar após a raquete");
+    let score = app.world().resource::<Score>();
+    assert_eq!((score.left, score.right), (0, 0));
+}
+
+#[test]
+fn paddle_hit_below_centre_sends_ball_downwards() {
+    let mut app = headless_app();
+    launch_ball_from(&mut app, Vec2::new(0., -30.), 0.);
+
+    step(&mut app, 130);
+
+    let (velocity, movement) = ball_state(&mut app);
+    assert!(velocity.x < 0., "a bola deveria voltar após a raquete");
+    assert!(
+        velocity.y < 0.,
+        "a bola deveria descer após tocar abaixo do centro"
+    );
+    assert!((velocity.length() - movement.speed).abs() < 0.1);
+}
+
+#[test]
+fn ball_at_max_speed_does_not_tunnel_through_paddle() {
+    let mut app = headless_app();
+    let max_speed = app.world().resource::<GameConfig>().max_ball_speed;
+    let mut ball = app
+        .world_mut()
+        .query_filtered::<&mut BallMovement, With<Ball>>();
+    ball.single_mut(app.world_mut()).speed = max_speed;
+    launch_ball(&mut app, 0.);
+
+    // Tempo suficiente para alcançar a raquete, mas não para cruzar a arena de volta
+    step(&mut app, 20);
+
+    let (velocity, movement) = ball_state(&mut app);
+    assert!(velocity.x < 0., "a bola deveria voltar após a raquete");
+    assert_eq!(movement.speed, max_speed); // O incremento não passa do limite
+    let score = app.world().resource::<Score>();
+    assert_eq!((score.left, score.right), (0, 0));
+}
+
+#[test]
+fn ai_controlled_left_paddle_returns_the_ball() {
+    let mut app = headless_app_with(|app| {
+        app.insert_resource(GameMode::SinglePlayer)
+            .insert_resource(AiSettings::from(Difficulty::Hard));
+    });
+    launch_ball(&mut app, 160.);
+
+    step(&mut app, 150);
+
+    let (velocity, _) = ball_state(&mut app);
+    assert!(velocity.x > 0., "a IA deveria rebater a bola");
+    let score = app.world().resource::<Score>();
+    assert_eq!((score.left, score.right), (0, 0));
+}
+
+#[test]
+fn paddle_follows_keyboard_and_stops_at_wall() {
+    let mut app = headless_app();
+    // Sem o `InputPlugin` a tecla permanece pressionada em todos os passos
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(KeyCode::ArrowUp);
+
+    step(&mut app, 100);
+
+    let arena = *app.world().resource::<Arena>();
+    let limit = arena.half_size().y - WALL_THICKNESS - PADDLE_HEIGHT / 2.;
+    let mut paddle = app.world_mut().query_filtered::<&Position, With<Player>>();
+    let y = paddle.single(app.world()).y;
+    assert!(y > limit - 10. && y <= limit + 10., "{y}");
+}
+
+#[test]
+fn reloaded_sizes_wait_for_the_next_match() {
+    let mut app = headless_app();
+    // Como ao recarregar o arquivo de configuração com o jogo aberto
+    let mut config = app.world_mut().resource_mut::<GameConfig>();
+    config.paddle_height = 10.;
+    config.wall_thickness = 0.;
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(KeyCode::ArrowUp);
+    step(&mut app, 100);
+
+    // A raquete continua parando na parede desta partida
+    let arena = *app.world().resource::<Arena>();
+    let limit = arena.half_size().y - WALL_THICKNESS - PADDLE_HEIGHT / 2.;
+    let mut paddle = app.world_mut().query_filtered::<&Position, With<Player>>();
+    let y = paddle.single(app.world()).y;
+    assert!(y > limit - 10. && y <= limit + 10., "{y}");
+
+    // A próxima partida é criada com as novas medidas
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::MainMenu);
+    step(&mut app, 1);
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Serving);
+    step(&mut app, 1);
+    let config = app.world().resource::<GameConfig>().clone();
+    assert_eq!(
+        *app.world().resource::<MatchGeometry>(),
+        MatchGeometry::new(&config)
+    );
+}
+
+#[test]
+fn same_seed_picks_the_same_serve_angle() {
+    let serve_angle = |seed| {
+        let mut app = headless_app_with(|app| {
+            app.insert_resource(GameRng::new(seed));
+        });
+        ball_state(&mut app).1.angle
+    };
+
+    assert_eq!(serve_angle(42), serve_angle(42));
+}
+
+#[test]
+fn serve_angles_stay_close_to_horizontal() {
+    let mut rng = StdRng::seed_from_u64(7);
+    for _ in 0..200 {
+        let angle = get_random_ball_start_angle(&mut rng).abs();
+        assert!((15.0..=45.0).contains(&angle), "{angle}");
+    }
+}
+
+#[test]
+fn steep_velocities_get_a_horizontal_component() {
+    let config = game_config();
+    let velocity = keep_rally_moving(Vec2::new(0., -500.), &config);
+    assert!(
+        velocity.x.abs() >= MIN_HORIZONTAL_SPEED - 0.01,
+        "{velocity}"
+    );
+    assert!(velocity.y < 0.);
+    assert!((velocity.length() - 500.).abs() < 0.01);
+
+    let velocity = keep_rally_moving(Vec2::new(-1000., 5000.), &config);
+    let angle = velocity.y.atan2(velocity.x.abs());
+    assert!(velocity.x < 0.);
+    assert!(angle <= MAX_BALL_ANGLE + 0.001, "{angle}");
+
+    // Uma bola já inclinada o bastante não muda
+    let velocity = Vec2::new(400., 300.);
+    assert!((keep_rally_moving(velocity, &config) - velocity).length() < 0.01);
+
+    // A inclinação máxima vem da configuração
+    let config = GameConfig {
+        max_ball_angle: std::f32::consts::FRAC_PI_4,
+        ..game_config()
+    };
+    let velocity = keep_rally_moving(Vec2::new(100., 5000.), &config);
+    let angle = velocity.y.atan2(velocity.x.abs());
+    assert!(angle <= std::f32::consts::FRAC_PI_4 + 0.001, "{angle}");
+}
+
+#[test]
+fn vertical_ball_leaves_the_wall_at_an_angle() {
+    let mut app = headless_app();
+    launch_ball(&mut app, 90.);
+
+    step(&mut app, 70);
+
+    let (velocity, movement) = ball_state(&mut app);
+    assert!(velocity.y < 0., "a bola deveria descer após a parede");
+    assert!(
+        velocity.x.abs() >= MIN_HORIZONTAL_SPEED - 0.01,
+        "{velocity}"
+    );
+    assert!((velocity.length() - movement.speed).abs() < 0.1);
+}
+
+#[test]
+fn stalled_rally_is_served_again() {
+    let mut app = headless_app();
+    // Uma bola parada em jogo nunca chega a uma raquete
+    let mut ball = app
+        .world_mut()
+        .query_filtered::<(&mut Position, &mut BallMovement), With<Ball>>();
+    let (mut position, mut movement) = ball.single_mut(app.world_mut());
+    position.0 = Vec2::new(300., 100.);
+    movement.speed = BALL_SPEED * 2.;
+
+    step(&mut app, (STALL_TIME * 64.) as u32 + 2);
+
+    let state = app.world().resource::<State<GameState>>();
+    assert_eq!(*state.get(), GameState::Serving);
+    let score = app.world().resource::<Score>();
+    assert_eq!((score.left, score.right), (0, 0));
+    let mut ball = app.world_mut().query_filtered::<&Position, With<Ball>>();
+    assert_eq!(ball.single(app.world()).0, Vec2::ZERO);
+    let (velocity, movement) = ball_state(&mut app);
+    assert_eq!(velocity, Vec2::ZERO);
+    assert_eq!(movement.speed, BALL_SPEED);
+}
+
+#[test]
+fn rally_guards_come_from_the_config() {
+    let mut app = headless_app_with(|app| {
+        app.insert_resource(GameConfig {
+            min_horizontal_speed: 300.,
+            stall_time: 2.,
+            ..game_config()
+        });
+    });
+    launch_ball(&mut app, 90.);
+    step(&mut app, 70);
+
+    let (velocity, _) = ball_state(&mut app);
+    assert!(velocity.x.abs() >= 300. - 0.01, "{velocity}");
+
+    // Sem toques de raquete, o saque volta depois de `stall_time` segundos
+    step(&mut app, 2 * 64 - 70 - 4);
+    let state = app.world().resource::<State<GameState>>();
+    assert_eq!(*state.get(), GameState::Playing);
+    step(&mut app, 8);
+    let state = app.world().resource::<State<GameState>>();
+    assert_eq!(*state.get(), GameState::Serving);
+}
+
+#[test]
+fn pausing_during_the_countdown_still_serves_the_ball() {
+    let mut app = headless_app_with(|app| {
+        app.insert_state(GameState::Serving);
+    });
+
+    tap(&mut app, KeyCode::Escape);
+    tap(&mut app, KeyCode::Escape);
+    assert_eq!(
+        *app.world().resource::<State<GameState>>().get(),
+        GameState::Serving
+    );
+    step(&mut app, 4 * 64);
+
+    let (velocity, movement) = ball_state(&mut app);
+    assert!(
+        (velocity.length() - movement.speed).abs() < 0.1,
+        "{velocity}"
+    );
+}
@@ -0,0 +1,185 @@
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::Ai;
+
+// Níveis de dificuldade da IA, cada um com um conjunto de ajustes em `AiSettings`. Como recurso,
+// guarda o nível escolhido no menu inicial
+#[derive(Resource, Debug, PartialEq, Eq, Copy, Clone, Default, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy, // Reage devagar, segue a bola sem prever e erra bastante
+    #[default]
+    Normal, // Prevê o ponto de chegada com alguma margem de erro
+    Hard, // Reage rápido, na velocidade máxima e sem errar
+}
+
+impl Difficulty {
+    // Próximo nível acima, parando no mais difícil
+    pub fn harder(self) -> Self {
+        match self {
+            Self::Easy => Self::Normal,
+            Self::Normal | Self::Hard => Self::Hard,
+        }
+    }
+
+    // Próximo nível abaixo, parando no mais fácil
+    pub fn easier(self) -> Self {
+        match self {
+            Self::Hard => Self::Normal,
+            Self::Normal | Self::Easy => Self::Easy,
+        }
+    }
+
+    // Nome mostrado no menu
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Easy => "Fácil",
+            Self::Normal => "Normal",
+            Self::Hard => "Difícil",
+        }
+    }
+}
+
+// Recurso com os ajustes da IA; inserido antes do `PongPlugin` para escolher a dificuldade
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AiSettings {
+    pub reaction_delay: f32, // Intervalo entre as decisões da IA, em segundos
+    pub max_speed: f32,      // Fração da velocidade da raquete que a IA usa (0 a 1)
+    pub prediction: bool,    // Se prevê onde a bola vai chegar, contando as rebatidas nas paredes
+    pub error_margin: f32,   // Erro máximo proposital no alvo, em unidades
+}
+
+impl From<Difficulty> for AiSettings {
+    fn from(difficulty: Difficulty) -> Self {
+        match difficulty {
+            Difficulty::Easy => Self {
+                reaction_delay: 0.3,
+                max_speed: 0.6,
+                prediction: false,
+                error_margin: 60.,
+            },
+            Difficulty::Normal => Self {
+                reaction_delay: 0.15,
+                max_speed: 0.85,
+                prediction: true,
+                error_margin: 30.,
+            },
+            Difficulty::Hard => Self {
+                reaction_delay: 0.05,
+                max_speed: 1.,
+                prediction: true,
+                error_margin: 0.,
+            },
+        }
+    }
+}
+
+impl Default for AiSettings {
+    fn default() -> Self {
+        Difficulty::default().into()
+    }
+}
+
+// O que a IA enxerga a cada passo, nas unidades de cada versão
+pub struct AiView {
+    pub paddle: Vec2,        // Posição da raquete
+    pub ball: Vec2,          // Posição da bola
+    pub ball_velocity: Vec2, // Velocidade da bola
+    pub floor: f32,          // Menor altura que o centro da bola alcança antes de rebater
+    pub ceiling: f32,        // Maior altura que o centro da bola alcança antes de rebater
+}
+
+// Distância até o alvo a partir da qual a raquete anda na velocidade máxima;
+// mais perto ela desacelera em vez de tremer em volta do alvo
+const STEERING_DISTANCE: f32 = 10.;
+
+// Componente com o estado da IA de uma raquete
+#[derive(Component, Debug, Default)]
+pub struct AiController {
+    pub target: f32,     // Altura para onde a raquete está indo
+    pub error: f32,      // Erro proposital no alvo, sorteado uma vez a cada aproximação da bola
+    since_decision: f32, // Tempo desde a última decisão, em segundos
+    approaching: bool,   // Se a bola vinha em direção à raquete no passo anterior
+}
+
+impl AiController {
+    // Atualiza o alvo quando passa o tempo de reação e devolve a direção da raquete,
+    // de -1 (para baixo na velocidade máxima) a 1 (para cima). O erro só muda quando a bola
+    // começa a vir em direção à raquete, para que o alvo não fique pulando durante a jogada
+    pub fn steer(
+        &mut self,
+        settings: &AiSettings,
+        view: &AiView,
+        delta_secs: f32,
+        rng: &mut impl Rng,
+    ) -> f32 {
+        let approaching = (view.paddle.x - view.ball.x) * view.ball_velocity.x > 0.;
+        if approaching && !self.approaching {
+            self.error = if settings.error_margin > 0. {
+                rng.random_range(-settings.error_margin..=settings.error_margin)
+            } else {
+                0.
+            };
+        }
+        self.approaching = approaching;
+
+        self.since_decision += delta_secs;
+        if self.since_decision >= settings.reaction_delay {
+            self.since_decision = 0.;
+            self.target = choose_target(settings, view) + self.error;
+        }
+
+        let direction = ((self.target - view.paddle.y) / STEERING_DISTANCE).clamp(-1., 1.);
+        direction * settings.max_speed
+    }
+}
+
+// Decide a altura para onde a raquete deve ir, antes do erro proposital
+fn choose_target(settings: &AiSettings, view: &AiView) -> f32 {
+    if !settings.prediction {
+        view.ball.y // Apenas segue a bola
+    } else {
+        // Espera no centro enquanto a bola se afasta
+        predict_intercept(
+            view.ball,
+            view.ball_velocity,
+            view.paddle.x,
+            view.floor,
+            view.ceiling,
+        )
+        .unwrap_or(0.)
+    }
+}
+
+// Calcula a altura em que a bola cruza a linha `x`, refletindo nas paredes entre `floor`
+// e `ceiling`. Devolve `None` se a bola não está indo em direção a essa linha.
+pub fn predict_intercept(
+    ball: Vec2,
+    velocity: Vec2,
+    x: f32,
+    floor: f32,
+    ceiling: f32,
+) -> Option<f32> {
+    let time = (x - ball.x) / velocity.x;
+    if !time.is_finite() || time < 0. {
+        return None;
+    }
+
+    // Desdobra as rebatidas: a altura "livre" vai e volta dentro da faixa
+    let span = ceiling - floor;
+    let free = ball.y + velocity.y * time - floor;
+    let folded = free.rem_euclid(2. * span);
+    Some(if folded <= span {
+        floor + folded
+    } else {
+        ceiling - (folded - span)
+    })
+}
+
+// Observador que dá um controlador a toda raquete marcada como `Ai`
+pub fn add_ai_controller(trigger: Trigger<OnAdd, Ai>, mut commands: Commands) {
+    commands
+        .entity(trigger.entity())
+        .insert(AiController::default());
+}
@@ -0,0 +1,80 @@
+use bevy::{prelude::*, render::camera::Viewport, window::PrimaryWindow};
+
+// Largura e altura padrão da arena, em unidades lógicas independentes da resolução
+pub const ARENA_WIDTH: f32 = 1920.;
+pub const ARENA_HEIGHT: f32 = 1080.;
+
+// Recurso com o tamanho lógico da arena, usado no lugar das consultas à janela.
+// Todos os sistemas de criação, movimento e pontuação usam esse tamanho, e a câmera
+// escala a arena para caber em qualquer janela.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct Arena {
+    pub size: Vec2, // Largura e altura da área de jogo
+}
+
+impl Default for Arena {
+    fn default() -> Self {
+        Self::new(ARENA_WIDTH, ARENA_HEIGHT)
+    }
+}
+
+impl Arena {
+    pub fn new(width: f32, height: f32) -> Self {
+        Self {
+            size: Vec2::new(width, height),
+        }
+    }
+
+    // Metade do tamanho da arena, útil já que o centro está na origem
+    pub fn half_size(&self) -> Vec2 {
+        self.size / 2.
+    }
+
+    pub fn width(&self) -> f32 {
+        self.size.x
+    }
+
+    pub fn height(&self) -> f32 {
+        self.size.y
+    }
+}
+
+// Calcula a maior área com a proporção da arena que cabe na janela, centralizada.
+// O restante da janela fica com faixas vazias (letterbox).
+pub fn letterbox_viewport(arena: &Arena, window_size: UVec2) -> Viewport {
+    let window_size = window_size.as_vec2();
+    let scale = (window_size / arena.size).min_element();
+    let size = (arena.size * scale).floor();
+    let position = ((window_size - size) / 2.).floor();
+
+    Viewport {
+        physical_position: position.as_uvec2(),
+        physical_size: size.as_uvec2().max(UVec2::ONE),
+        ..default()
+    }
+}
+
+// Sistema que ajusta a área de desenho da câmera sempre que a janela muda de tamanho
+pub fn fit_camera_to_window(
+    arena: Res<Arena>,
+    window: Query<Ref<Window>, With<PrimaryWindow>>,
+    mut cameras: Query<&mut Camera, With<Camera2d>>,
+) {
+    // Sem janela (modo headless) não há o que ajustar
+    let Ok(window) = window.get_single() else {
+        return;
+    };
+    // Também ajusta câmeras recém-criadas, antes de qualquer redimensionamento
+    let new_camera = cameras.iter().any(|camera| camera.viewport.is_none());
+    if !window.is_changed() && !arena.is_changed() && !new_camera {
+        return;
+    }
+    if window.physical_width() == 0 || window.physical_height() == 0 {
+        return; // Janela minimizada
+    }
+
+    let viewport = letterbox_viewport(&arena, window.physical_size());
+    for mut camera in &mut cameras {
+        camera.viewport = Some(viewport.clone());
+    }
+}
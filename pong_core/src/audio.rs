@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+use bevy::{audio::Volume, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{MatchWon, Scored};
+
+// O que a bola atingiu
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum HitKind {
+    Paddle, // Uma das raquetes
+    Wall,   // Uma das paredes ou barreiras
+}
+
+// Evento enviado pela versão a cada reflexão da bola, usado pelos efeitos sonoros e visuais.
+// `pitch` multiplica a velocidade do som: 1 é o tom normal e valores maiores deixam o som mais
+// agudo
+#[derive(Event, Debug, PartialEq, Copy, Clone)]
+pub struct BallHit {
+    pub kind: HitKind,
+    pub position: Vec2, // Posição da bola no toque
+    pub pitch: f32,
+}
+
+impl BallHit {
+    // Reflexão com o tom normal
+    pub fn new(kind: HitKind, position: Vec2) -> Self {
+        Self {
+            kind,
+            position,
+            pitch: 1.,
+        }
+    }
+}
+
+// Recurso com o volume dos efeitos sonoros, de 0 (mudo) a 1
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SoundSettings {
+    pub volume: f32,
+}
+
+impl Default for SoundSettings {
+    fn default() -> Self {
+        Self { volume: 0.5 }
+    }
+}
+
+// Recurso com os tons de cada efeito, gerados sem arquivos de áudio. As frequências da raquete,
+// da parede e do ponto são as do Pong original
+#[derive(Resource)]
+pub struct SoundEffects {
+    pub paddle: Handle<Pitch>, // Bola na raquete
+    pub wall: Handle<Pitch>,   // Bola na parede
+    pub score: Handle<Pitch>,  // Ponto marcado
+    pub serve: Handle<Pitch>,  // Saque
+    pub win: Handle<Pitch>,    // Fim da partida
+}
+
+// Sistema que cria os tons quando há áudio; sem o `AudioPlugin` (como no modo headless) o jogo
+// segue em silêncio
+pub fn load_sound_effects(mut commands: Commands, pitches: Option<ResMut<Assets<Pitch>>>) {
+    let Some(mut pitches) = pitches else {
+        return;
+    };
+    let mut tone =
+        |frequency, millis| pitches.add(Pitch::new(frequency, Duration::from_millis(millis)));
+    commands.insert_resource(SoundEffects {
+        paddle: tone(459., 96),
+        wall: tone(226., 16),
+        score: tone(490., 257),
+        serve: tone(660., 48),
+        win: tone(880., 600),
+    });
+}
+
+// Sistema que toca os efeitos dos eventos do quadro. No ponto que encerra a partida toca só o
+// som de vitória
+pub fn play_sound_effects(
+    mut commands: Commands,
+    mut hits: EventReader<BallHit>,
+    mut scored: EventReader<Scored>,
+    mut match_won: EventReader<MatchWon>,
+    sounds: Res<SoundEffects>,
+    settings: Res<SoundSettings>,
+) {
+    let mut play =
+        |sound: &Handle<Pitch>, pitch: f32| play_tone(&mut commands, sound, &settings, pitch);
+
+    for hit in hits.read() {
+        let sound = match hit.kind {
+            HitKind::Paddle => &sounds.paddle,
+            HitKind::Wall => &sounds.wall,
+        };
+        play(sound, hit.pitch);
+    }
+
+    let won = match_won.read().count() > 0;
+    let points = scored.read().count();
+    if won {
+        play(&sounds.win, 1.);
+    } else if points > 0 {
+        play(&sounds.score, 1.);
+    }
+}
+
+// Sistema que toca o som do saque quando a bola é posta em jogo
+pub fn play_serve_sound(
+    mut commands: Commands,
+    sounds: Res<SoundEffects>,
+    settings: Res<SoundSettings>,
+) {
+    play_tone(&mut commands, &sounds.serve, &settings, 1.);
+}
+
+// Toca um tom uma vez com o volume escolhido, removendo a entidade ao terminar
+fn play_tone(commands: &mut Commands, sound: &Handle<Pitch>, settings: &SoundSettings, pitch: f32) {
+    commands.spawn((
+        AudioPlayer(sound.clone()),
+        PlaybackSettings::DESPAWN
+            .with_volume(Volume::new(settings.volume.clamp(0., 1.)))
+            .with_speed(pitch),
+    ));
+}
@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+
+use crate::GameConfig;
+
+// Calcula a velocidade da bola após bater na face de uma raquete. O ângulo de saída depende de
+// onde a bola tocou em relação ao centro da raquete (no centro sai reta, nas pontas sai com o
+// ângulo máximo) e de parte da velocidade vertical da raquete, somada à da bola.
+// `away` é o sentido horizontal de saída (1 para a direita, -1 para a esquerda) e a velocidade
+// devolvida mantém o módulo de `incoming`.
+pub fn paddle_bounce(
+    incoming: Vec2,
+    away: f32,
+    hit_offset: f32,
+    paddle_half_height: f32,
+    paddle_velocity: f32,
+    config: &GameConfig,
+) -> Vec2 {
+    let speed = incoming.length();
+
+    // Posição do contato de -1 (ponta de baixo) a 1 (ponta de cima)
+    let relative = (hit_offset / paddle_half_height).clamp(-1., 1.);
+    let mut outgoing = Vec2::from_angle(relative * config.max_bounce_angle) * speed;
+
+    // Efeito: a bola leva parte do movimento vertical da raquete
+    outgoing.y += paddle_velocity * config.paddle_spin;
+
+    let angle = outgoing
+        .y
+        .atan2(outgoing.x)
+        .clamp(-config.max_bounce_angle, config.max_bounce_angle);
+    Vec2::new(away.signum() * angle.cos(), angle.sin()) * speed
+}
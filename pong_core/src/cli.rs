@@ -0,0 +1,248 @@
+use std::{net::SocketAddr, path::PathBuf, str::FromStr};
+
+use bevy::prelude::*;
+
+use crate::{
+    run_simulation, start_replay, AiSettings, Difficulty, DisplaySettings, EffectSettings,
+    GameMode, Replay, ReplayRecorder, Score, SoundSettings, SpectatorServer, WindowChoice,
+};
+
+// Texto de ajuda mostrado com `--help` ou após uma opção inválida
+pub const USAGE: &str = "\
+Opções:
+  --windowed | --fullscreen | --borderless   modo da janela
+  --monitor N             monitor da tela cheia (0 é o primeiro; o principal se não existir)
+  --resolution LxA        tamanho da janela, como 1280x720
+  --mode single|two       contra a IA ou dois jogadores (--two-players é o mesmo que --mode two)
+  --difficulty easy|normal|hard   dificuldade da IA
+  --seed N                semente do gerador aleatório
+  --config arquivo        arquivo de configuração do jogo
+  --headless              simula a partida sem janela até o fim e mostra o placar
+  --sim N                 como --headless, parando após N passos
+  --volume V              volume dos efeitos sonoros, de 0 a 1
+  --no-effects            desliga os efeitos visuais
+  --shake                 treme a tela a cada ponto
+  --record arquivo        grava a partida ao fechar o jogo
+  --replay arquivo        repete uma gravação
+  --spectators porta      transmite a partida para o binário `spectator`
+  --peer endereço         joga online contra outra instância (só no pong simples)
+  --port porta            porta local do jogo online (7000 por padrão)
+  --player 0|1            lado do jogo online: 0 é a direita e 1 a esquerda
+  --help                  mostra esta ajuda";
+
+// Opções da linha de comando comuns aos binários, lidas antes de montar o `App`. As ausentes
+// ficam com o padrão de cada versão
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CliOptions {
+    pub window: Option<WindowChoice>,
+    pub monitor: Option<usize>,
+    pub resolution: Option<UVec2>,
+    pub mode: Option<GameMode>,
+    pub difficulty: Option<Difficulty>,
+    pub seed: Option<u64>,
+    pub config: Option<PathBuf>,
+    pub headless: bool,
+    pub sim_steps: Option<u32>,
+    pub volume: Option<f32>,
+    pub no_effects: bool,
+    pub shake: bool,
+    pub record: Option<PathBuf>,
+    pub replay: Option<PathBuf>,
+    pub spectators: Option<u16>,
+    pub peer: Option<SocketAddr>,
+    pub port: Option<u16>,
+    pub player: Option<usize>,
+    pub help: bool,
+}
+
+impl CliOptions {
+    // Lê as opções, sem o nome do programa. Devolve uma mensagem clara para opções
+    // desconhecidas, valores ausentes ou inválidos
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("{arg} precisa de um valor"))
+            };
+            match arg.as_str() {
+                "--windowed" => options.window = Some(WindowChoice::Windowed),
+                "--fullscreen" => options.window = Some(WindowChoice::Fullscreen),
+                "--borderless" => options.window = Some(WindowChoice::Borderless),
+                "--monitor" => options.monitor = Some(parse_value(&arg, &value()?)?),
+                "--resolution" => options.resolution = Some(parse_resolution(&value()?)?),
+                "--mode" => options.mode = Some(parse_mode(&value()?)?),
+                "--two-players" => options.mode = Some(GameMode::TwoPlayers),
+                "--difficulty" => options.difficulty = Some(parse_difficulty(&value()?)?),
+                "--seed" => options.seed = Some(parse_value(&arg, &value()?)?),
+                "--config" => options.config = Some(value()?.into()),
+                "--headless" => options.headless = true,
+                "--sim" => options.sim_steps = Some(parse_value(&arg, &value()?)?),
+                "--volume" => options.volume = Some(parse_value(&arg, &value()?)?),
+                "--no-effects" => options.no_effects = true,
+                "--shake" => options.shake = true,
+                "--record" => options.record = Some(value()?.into()),
+                "--replay" => options.replay = Some(value()?.into()),
+                "--spectators" => options.spectators = Some(parse_value(&arg, &value()?)?),
+                "--peer" => options.peer = Some(parse_value(&arg, &value()?)?),
+                "--port" => options.port = Some(parse_value(&arg, &value()?)?),
+                "--player" => options.player = Some(parse_value(&arg, &value()?)?),
+                "--help" | "-h" => options.help = true,
+                _ => return Err(format!("opção desconhecida: {arg}")),
+            }
+        }
+
+        if options.player.is_some_and(|player| player > 1) {
+            return Err("--player deve ser 0 ou 1".into());
+        }
+        if options.replay.is_some() && options.record.is_some() {
+            return Err("--record e --replay não podem ser usados juntos".into());
+        }
+        Ok(options)
+    }
+
+    // Lê as opções do programa; com `--help` mostra a ajuda e, com uma opção inválida, mostra
+    // o erro e a ajuda e encerra
+    pub fn from_env() -> Self {
+        match Self::parse(std::env::args().skip(1)) {
+            Ok(options) if options.help => {
+                println!("{USAGE}");
+                std::process::exit(0);
+            }
+            Ok(options) => options,
+            Err(error) => {
+                eprintln!("{error}\n\n{USAGE}");
+                std::process::exit(2);
+            }
+        }
+    }
+
+    // Se a partida roda sem janela
+    pub fn headless(&self) -> bool {
+        self.headless || self.sim_steps.is_some()
+    }
+
+    // Janela pedida, com o modo padrão da versão se nenhum foi escolhido
+    pub fn display(&self, default_mode: WindowChoice) -> DisplaySettings {
+        DisplaySettings {
+            mode: self.window.unwrap_or(default_mode),
+            monitor: self.monitor,
+            resolution: self.resolution,
+        }
+    }
+
+    // Insere a dificuldade pedida, se houver, com os ajustes da IA correspondentes; o menu
+    // inicial começa nela
+    pub fn insert_difficulty(&self, app: &mut App) {
+        if let Some(difficulty) = self.difficulty {
+            app.insert_resource(difficulty)
+                .insert_resource(AiSettings::from(difficulty));
+        }
+    }
+
+    // Volume dos efeitos sonoros
+    pub fn sound(&self) -> SoundSettings {
+        self.volume
+            .map_or_else(SoundSettings::default, |volume| SoundSettings { volume })
+    }
+
+    // Efeitos visuais: todos desligados com `--no-effects`, tremor da tela só com `--shake`
+    pub fn effects(&self) -> EffectSettings {
+        let mut effects = if self.no_effects {
+            EffectSettings::none()
+        } else {
+            EffectSettings::default()
+        };
+        effects.screen_shake |= self.shake;
+        effects
+    }
+
+    // Transmissão, gravação e repetição de partidas pedidas na linha de comando. Roda depois do
+    // plugin da versão; um erro ao abrir a porta ou ler a gravação é mostrado e encerra
+    pub fn apply_extras(&self, app: &mut App) {
+        // `--spectators porta` transmite a partida para o binário `spectator`
+        if let Some(port) = self.spectators {
+            match SpectatorServer::bind(("0.0.0.0", port)) {
+                Ok(server) => {
+                    app.insert_resource(server);
+                }
+                Err(error) => {
+                    eprintln!("Não foi possível abrir a porta {port} para espectadores: {error}");
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        // `--record arquivo` grava a partida ao fechar o jogo; `--replay arquivo` repete uma gravação
+        if let Some(path) = &self.record {
+            app.insert_resource(ReplayRecorder::new(path));
+        }
+        if let Some(path) = &self.replay {
+            match Replay::load(path) {
+                Ok(replay) => start_replay(app, replay),
+                Err(error) => {
+                    eprintln!(
+                        "Não foi possível ler a gravação {}: {error}",
+                        path.display()
+                    );
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+}
+
+// Inicia o jogo montado pelo binário. Sem janela, simula a partida até o fim (ou até `--sim`
+// passos), salva a gravação, se houver, e mostra o placar; com janela, abre o loop do Bevy
+pub fn run_app(mut app: App, options: &CliOptions) {
+    if !options.headless() {
+        app.run();
+        return;
+    }
+
+    let steps = run_simulation(&mut app, options.sim_steps);
+    app.world_mut().send_event(AppExit::Success); // Salva a gravação, se houver
+    app.update();
+    let score = app.world().resource::<Score>();
+    println!(
+        "{steps} passos: esquerda {} x {} direita (sets {} x {})",
+        score.left, score.right, score.left_sets, score.right_sets
+    );
+}
+
+fn parse_value<T: FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("valor inválido para {name}: {value}"))
+}
+
+// Resolução no formato `LARGURAxALTURA`
+fn parse_resolution(value: &str) -> Result<UVec2, String> {
+    let invalid = || format!("resolução inválida: {value} (use, por exemplo, 1280x720)");
+    let (width, height) = value.split_once(['x', 'X']).ok_or_else(invalid)?;
+    match (width.parse(), height.parse()) {
+        (Ok(width), Ok(height)) if width > 0 && height > 0 => Ok(UVec2::new(width, height)),
+        _ => Err(invalid()),
+    }
+}
+
+fn parse_mode(value: &str) -> Result<GameMode, String> {
+    match value {
+        "single" => Ok(GameMode::SinglePlayer),
+        "two" => Ok(GameMode::TwoPlayers),
+        _ => Err(format!("modo inválido: {value} (use single ou two)")),
+    }
+}
+
+fn parse_difficulty(value: &str) -> Result<Difficulty, String> {
+    match value {
+        "easy" => Ok(Difficulty::Easy),
+        "normal" => Ok(Difficulty::Normal),
+        "hard" => Ok(Difficulty::Hard),
+        _ => Err(format!(
+            "dificuldade inválida: {value} (use easy, normal ou hard)"
+        )),
+    }
+}
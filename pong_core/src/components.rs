@@ -0,0 +1,35 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Componente para representar a bola no jogo
+#[derive(Component)]
+pub struct Ball;
+
+// Componente para representar as raquetes
+#[derive(Component)]
+pub struct Paddle;
+
+// Componente para identificar a raquete do jogador principal (direita)
+#[derive(Component)]
+pub struct Player;
+
+// Componente para identificar a raquete controlada pela IA
+#[derive(Component)]
+pub struct Ai;
+
+// Recurso com o modo de jogo: quem controla a raquete da esquerda
+#[derive(Resource, Debug, PartialEq, Eq, Copy, Clone, Default, Serialize, Deserialize)]
+pub enum GameMode {
+    #[default]
+    SinglePlayer, // Jogador contra a IA
+    TwoPlayers, // Dois jogadores no mesmo teclado, o da esquerda com W/S
+}
+
+// Recurso com a bola ao fim do último passo fixo, em unidades e unidades por segundo. Cada versão
+// o preenche com os próprios componentes de física; a gravação de partidas e os espectadores
+// leem daqui
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub struct BallSample {
+    pub position: Vec2, // Posição da bola
+    pub velocity: Vec2, // Velocidade da bola
+}
@@ -0,0 +1,267 @@
+use std::{
+    f32::consts::FRAC_PI_2,
+    fs, io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use bevy::prelude::*;
+
+use crate::{Arena, GameConfig, SpeedCurve};
+
+pub const CONFIG_POLL_INTERVAL: f32 = 0.5; // Intervalo entre as verificações do arquivo, em segundos
+
+impl GameConfig {
+    // Lê a configuração de um arquivo RON e confere se os valores fazem sentido
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        let config: Self = ron::from_str(&text).map_err(io::Error::other)?;
+        config
+            .validate()
+            .map_err(|problems| io::Error::new(io::ErrorKind::InvalidData, problems))?;
+        Ok(config)
+    }
+
+    // Grava a configuração em um arquivo RON, legível para edição manual
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let text = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(io::Error::other)?;
+        fs::write(path, text)
+    }
+
+    // Confere os valores, devolvendo todos os problemas encontrados em uma só mensagem
+    pub fn validate(&self) -> Result<(), String> {
+        let mut problems = Vec::new();
+
+        let positive = [
+            ("ball_radius", self.ball_radius),
+            ("ball_speed", self.ball_speed),
+            ("paddle_speed", self.paddle_speed),
+            ("paddle_width", self.paddle_width),
+            ("paddle_height", self.paddle_height),
+            ("max_ball_speed", self.max_ball_speed),
+            ("stall_time", self.stall_time),
+        ];
+        for (name, value) in positive {
+            let valid = value.is_finite() && value > 0.;
+            if !valid {
+                problems.push(format!("`{name}` deve ser maior que zero (valor: {value})"));
+            }
+        }
+
+        let non_negative = [
+            ("speed_increment", self.speed_increment),
+            ("wall_thickness", self.wall_thickness),
+            ("paddle_padding", self.paddle_padding),
+            ("paddle_spin", self.paddle_spin),
+            ("serve_delay", self.serve_delay),
+            ("min_horizontal_speed", self.min_horizontal_speed),
+        ];
+        for (name, value) in non_negative {
+            let valid = value.is_finite() && value >= 0.;
+            if !valid {
+                problems.push(format!("`{name}` não pode ser negativo (valor: {value})"));
+            }
+        }
+
+        let angle = self.max_bounce_angle;
+        let valid = angle > 0. && angle < FRAC_PI_2;
+        if !valid {
+            problems.push(format!(
+                "`max_bounce_angle` deve ficar entre 0 e {FRAC_PI_2} radianos (valor: {angle})"
+            ));
+        }
+        let angle = self.max_ball_angle;
+        let valid = angle >= self.max_bounce_angle && angle < FRAC_PI_2;
+        if !valid {
+            problems.push(format!(
+                "`max_ball_angle` deve ficar entre `max_bounce_angle` ({}) e {FRAC_PI_2} radianos (valor: {angle})",
+                self.max_bounce_angle
+            ));
+        }
+        match self.speed_curve {
+            SpeedCurve::Linear => {}
+            SpeedCurve::Multiplicative(factor) => {
+                let valid = factor.is_finite() && factor >= 1.;
+                if !valid {
+                    problems.push(format!(
+                        "o fator de `Multiplicative` deve ser pelo menos 1 (valor: {factor})"
+                    ));
+                }
+            }
+            SpeedCurve::Logarithmic(scale) => {
+                let valid = scale.is_finite() && scale > 0.;
+                if !valid {
+                    problems.push(format!(
+                        "a escala de `Logarithmic` deve ser maior que zero (valor: {scale})"
+                    ));
+                }
+            }
+            SpeedCurve::Capped(cap) => {
+                let valid = cap.is_finite() && cap >= self.ball_speed;
+                if !valid {
+                    problems.push(format!(
+                        "o teto de `Capped` ({cap}) deve ser pelo menos `ball_speed` ({})",
+                        self.ball_speed
+                    ));
+                }
+            }
+        }
+        if self.ball_speed >= self.max_ball_speed {
+            problems.push(format!(
+                "`ball_speed` ({}) deve ser menor que `max_ball_speed` ({})",
+                self.ball_speed, self.max_ball_speed
+            ));
+        }
+
+        // Paredes, raquetes e bola precisam caber na altura da arena, com espaço para se mover
+        let arena = Arena::default();
+        let geometry = MatchGeometry::new(self);
+        let valid = geometry.paddle_limit(&arena) > 0.;
+        if !valid {
+            problems.push(format!(
+                "as paredes (`wall_thickness`: {}) e as raquetes (`paddle_height`: {}) não cabem na altura da arena ({})",
+                self.wall_thickness,
+                self.paddle_height,
+                arena.height()
+            ));
+        }
+        let valid = geometry.ball_limit(&arena) > 0.;
+        if !valid {
+            problems.push(format!(
+                "as paredes (`wall_thickness`: {}) e a bola (`ball_radius`: {}) não cabem na altura da arena ({})",
+                self.wall_thickness,
+                self.ball_radius,
+                arena.height()
+            ));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems.join("; "))
+        }
+    }
+
+    // Lê a configuração do arquivo; se ele não existir, grava a configuração padrão da versão
+    // para servir de modelo. Um arquivo inválido é avisado e ignorado. Roda antes do `App`
+    // existir, por isso os avisos vão direto para a saída de erro.
+    pub fn load_or_create(path: impl AsRef<Path>, defaults: Self) -> Self {
+        let path = path.as_ref();
+        match Self::load(path) {
+            Ok(config) => config,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                if let Err(error) = defaults.save(path) {
+                    eprintln!("Não foi possível gravar {}: {error}", path.display());
+                }
+                defaults
+            }
+            Err(error) => {
+                eprintln!("Configuração inválida em {}: {error}", path.display());
+                defaults
+            }
+        }
+    }
+}
+
+// Recurso com o arquivo de configuração a acompanhar; inserido pelo binário para recarregar o
+// `GameConfig` sempre que o arquivo for salvo
+#[derive(Resource)]
+pub struct ConfigFile {
+    path: PathBuf,                // Arquivo acompanhado
+    modified: Option<SystemTime>, // Última modificação já lida
+    timer: Timer,                 // Intervalo entre as verificações
+}
+
+impl ConfigFile {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        Self {
+            modified: modified_time(&path),
+            path,
+            timer: Timer::from_seconds(CONFIG_POLL_INTERVAL, TimerMode::Repeating),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+// Momento da última modificação do arquivo, se ele existir
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+// Recurso com as medidas da partida em andamento, copiadas do `GameConfig` no início de cada
+// partida. Paredes, raquetes e bola são criadas com essas medidas, então os sistemas da partida
+// as leem daqui: uma configuração recarregada no meio do jogo só muda as medidas na próxima
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct MatchGeometry {
+    pub ball_radius: f32,    // Raio da bola
+    pub wall_thickness: f32, // Espessura das paredes superior e inferior
+    pub paddle_size: Vec2,   // Largura e altura das raquetes
+}
+
+impl Default for MatchGeometry {
+    fn default() -> Self {
+        Self::new(&GameConfig::default())
+    }
+}
+
+impl MatchGeometry {
+    pub fn new(config: &GameConfig) -> Self {
+        Self {
+            ball_radius: config.ball_radius,
+            wall_thickness: config.wall_thickness,
+            paddle_size: config.paddle_size(),
+        }
+    }
+
+    // Maior distância vertical do centro de uma raquete até o meio da arena, sem entrar nas paredes
+    pub fn paddle_limit(&self, arena: &Arena) -> f32 {
+        arena.half_size().y - self.wall_thickness - self.paddle_size.y / 2.
+    }
+
+    // Maior distância vertical do centro da bola até o meio da arena, sem entrar nas paredes
+    pub fn ball_limit(&self, arena: &Arena) -> f32 {
+        arena.half_size().y - self.wall_thickness - self.ball_radius
+    }
+}
+
+// Sistema que fixa as medidas da partida a partir da configuração atual
+pub fn store_match_geometry(mut geometry: ResMut<MatchGeometry>, config: Res<GameConfig>) {
+    *geometry = MatchGeometry::new(&config);
+}
+
+// Sistema que recarrega o `GameConfig` quando o arquivo muda. Uma configuração inválida é
+// avisada e a anterior continua valendo. Usa o tempo real para funcionar também na pausa
+pub fn reload_config(
+    mut file: ResMut<ConfigFile>,
+    mut config: ResMut<GameConfig>,
+    time: Res<Time<Real>>,
+) {
+    if !file.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    let modified = modified_time(&file.path);
+    if modified == file.modified {
+        return;
+    }
+    file.modified = modified;
+
+    match GameConfig::load(&file.path) {
+        Ok(new_config) => {
+            if *config != new_config {
+                *config = new_config;
+                info!("Configuração recarregada de {}", file.path.display());
+            }
+        }
+        Err(error) => error!(
+            "Configuração inválida em {}, mantendo a anterior: {error}",
+            file.path.display()
+        ),
+    }
+}
@@ -0,0 +1,82 @@
+use bevy::{
+    prelude::*,
+    window::{Monitor, MonitorSelection, PrimaryWindow, WindowMode, WindowResolution},
+};
+
+// Modo da janela escolhido na linha de comando
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WindowChoice {
+    #[default]
+    Windowed, // Janela comum
+    Fullscreen, // Tela cheia exclusiva
+    Borderless, // Janela sem bordas do tamanho do monitor
+}
+
+// Recurso com a janela pedida pelo binário: modo, monitor e resolução. A janela abre no modo
+// janela e `apply_display_settings` aplica o pedido assim que os monitores são conhecidos,
+// voltando para o monitor principal se o pedido não existir
+#[derive(Resource, Debug, Clone, PartialEq, Default)]
+pub struct DisplaySettings {
+    pub mode: WindowChoice,
+    pub monitor: Option<usize>, // Índice do monitor; o principal se ausente
+    pub resolution: Option<UVec2>, // Tamanho da janela ou da tela cheia, em pixels
+}
+
+impl DisplaySettings {
+    // Janela inicial para o `WindowPlugin`, ainda sem tela cheia
+    pub fn window(&self, title: &str) -> Window {
+        let mut window = Window {
+            title: title.into(),
+            ..default()
+        };
+        if let Some(size) = self.resolution {
+            window.resolution = WindowResolution::new(size.x as f32, size.y as f32);
+        }
+        window
+    }
+
+    // Modo da janela no monitor escolhido
+    pub fn window_mode(&self, monitor: MonitorSelection) -> WindowMode {
+        match (self.mode, self.resolution) {
+            (WindowChoice::Windowed, _) => WindowMode::Windowed,
+            (WindowChoice::Borderless, _) => WindowMode::BorderlessFullscreen(monitor),
+            (WindowChoice::Fullscreen, Some(_)) => WindowMode::SizedFullscreen(monitor), // Usa a resolução pedida
+            (WindowChoice::Fullscreen, None) => WindowMode::Fullscreen(monitor),
+        }
+    }
+}
+
+// Monitor a usar: o pedido, se existir entre os `count` conectados, ou o principal
+pub fn choose_monitor(requested: Option<usize>, count: usize) -> MonitorSelection {
+    match requested {
+        Some(index) if index < count => MonitorSelection::Index(index),
+        Some(index) => {
+            warn!("Monitor {index} não encontrado ({count} conectados); usando o principal");
+            MonitorSelection::Primary
+        }
+        None => MonitorSelection::Primary,
+    }
+}
+
+// Sistema que aplica a janela pedida uma única vez, quando os monitores já foram listados
+pub fn apply_display_settings(
+    settings: Res<DisplaySettings>,
+    monitors: Query<(), With<Monitor>>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    mut applied: Local<bool>,
+) {
+    let count = monitors.iter().count();
+    if *applied || count == 0 {
+        return;
+    }
+    let Ok(mut window) = windows.get_single_mut() else {
+        return;
+    };
+
+    let monitor = choose_monitor(settings.monitor, count);
+    window.mode = settings.window_mode(monitor);
+    if settings.mode == WindowChoice::Windowed {
+        window.position = WindowPosition::Centered(monitor);
+    }
+    *applied = true;
+}
@@ -0,0 +1,367 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Ball, BallHit, BallSample, GameConfig, GameState, HitKind, InGame, MatchGeometry, Paddle,
+    Scored,
+};
+
+pub const TRAIL_TIME: f32 = 0.1; // Duração do rastro da bola na velocidade `TRAIL_SPEED`, em segundos
+pub const TRAIL_SPEED: f32 = 500.; // Velocidade da bola de referência para o rastro
+pub const MIN_TRAIL_TIME: f32 = 0.05; // Duração mínima do rastro, nas bolas mais lentas
+pub const MAX_TRAIL_TIME: f32 = 0.4; // Duração máxima do rastro, nas bolas mais rápidas
+pub const FLASH_TIME: f32 = 0.15; // Duração do brilho da raquete atingida
+pub const PARTICLES_PER_HIT: usize = 8; // Partículas soltas a cada toque
+pub const PARTICLE_TIME: f32 = 0.3; // Duração das partículas
+pub const PARTICLE_SPEED: f32 = 150.; // Velocidade máxima das partículas
+pub const SHAKE_TIME: f32 = 0.3; // Duração do tremor da tela após um ponto
+pub const SHAKE_STRENGTH: f32 = 8.; // Deslocamento máximo da câmera no tremor
+pub const FADE_LEVELS: usize = 16; // Níveis de transparência dos materiais compartilhados pelos efeitos
+
+// Recurso com os efeitos visuais ligados. Cada um pode ser desligado por acessibilidade; o tremor
+// da tela começa desligado
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EffectSettings {
+    pub trail: bool,        // Rastro da bola
+    pub flashes: bool,      // Brilho da raquete atingida
+    pub particles: bool,    // Partículas nos pontos de contato
+    pub screen_shake: bool, // Tremor da tela a cada ponto
+}
+
+impl Default for EffectSettings {
+    fn default() -> Self {
+        Self {
+            trail: true,
+            flashes: true,
+            particles: true,
+            screen_shake: false,
+        }
+    }
+}
+
+impl EffectSettings {
+    // Todos os efeitos desligados
+    pub fn none() -> Self {
+        Self {
+            trail: false,
+            flashes: false,
+            particles: false,
+            screen_shake: false,
+        }
+    }
+}
+
+// Cor de um efeito, escolhendo qual conjunto de materiais de `EffectAssets` ele usa
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EffectColor {
+    Ball,  // Cor da bola, no rastro
+    White, // Brilho das raquetes e partículas
+}
+
+// Recurso com as malhas e os materiais compartilhados por todos os efeitos, criados uma vez no
+// início. Cada cor tem `FADE_LEVELS` materiais, do transparente ao opaco, e os efeitos trocam
+// de material ao sumir em vez de criar ou alterar um material próprio
+#[derive(Resource)]
+pub struct EffectAssets {
+    pub circle: Handle<Mesh>, // Círculo de raio 1, escalado para o tamanho da bola
+    pub square: Handle<Mesh>, // Quadrado de lado 1, escalado para cada efeito
+    pub ball: Vec<Handle<ColorMaterial>>, // Cor da bola em cada nível de transparência
+    pub white: Vec<Handle<ColorMaterial>>, // Branco em cada nível de transparência
+}
+
+impl EffectAssets {
+    // Material da cor com a transparência mais próxima de `alpha`
+    pub fn material(&self, color: EffectColor, alpha: f32) -> Handle<ColorMaterial> {
+        let level = (alpha.clamp(0., 1.) * (FADE_LEVELS - 1) as f32).round() as usize;
+        match color {
+            EffectColor::Ball => self.ball[level].clone(),
+            EffectColor::White => self.white[level].clone(),
+        }
+    }
+}
+
+// Transparência de cada nível dos materiais
+fn fade_level_alpha(level: usize) -> f32 {
+    level as f32 / (FADE_LEVELS - 1) as f32
+}
+
+// Sistema que cria as malhas e os materiais compartilhados pelos efeitos
+pub fn load_effect_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    config: Res<GameConfig>,
+) {
+    let mut palette = |color: Color| -> Vec<_> {
+        (0..FADE_LEVELS)
+            .map(|level| materials.add(color.with_alpha(fade_level_alpha(level))))
+            .collect()
+    };
+    commands.insert_resource(EffectAssets {
+        circle: meshes.add(Circle::new(1.)),
+        square: meshes.add(Rectangle::from_length(1.)),
+        ball: palette(config.ball_color),
+        white: palette(Color::WHITE),
+    });
+}
+
+// Sistema que acompanha a cor da bola no rastro quando a configuração é recarregada
+pub fn update_trail_color(
+    assets: Res<EffectAssets>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    config: Res<GameConfig>,
+) {
+    for (level, handle) in assets.ball.iter().enumerate() {
+        if let Some(material) = materials.get_mut(handle) {
+            material.color = config.ball_color.with_alpha(fade_level_alpha(level));
+        }
+    }
+}
+
+// Componente de um efeito que some aos poucos: a transparência cai até zero e, se `shrink`,
+// o tamanho também. A entidade é removida ao fim do tempo
+#[derive(Component)]
+pub struct Fade {
+    pub timer: Timer,
+    pub alpha: f32,         // Transparência inicial
+    pub color: EffectColor, // Materiais usados ao sumir
+    pub size: Vec3,         // Escala inicial
+    pub shrink: bool,       // Se diminui junto com a transparência
+}
+
+impl Fade {
+    fn new(seconds: f32, alpha: f32, color: EffectColor, size: Vec2, shrink: bool) -> Self {
+        Self {
+            timer: Timer::from_seconds(seconds, TimerMode::Once),
+            alpha,
+            color,
+            size: size.extend(1.),
+            shrink,
+        }
+    }
+}
+
+// Componente de um ponto do rastro da bola
+#[derive(Component)]
+pub struct TrailDot;
+
+// Componente de uma partícula solta num toque, com a própria velocidade
+#[derive(Component)]
+pub struct Particle(pub Vec2);
+
+// Componente do brilho sobre a raquete atingida
+#[derive(Component)]
+pub struct PaddleFlash;
+
+// Recurso com o tremor da tela em andamento
+#[derive(Resource)]
+pub struct ScreenShake(pub Timer);
+
+// Duração do rastro para a velocidade da bola: quanto mais rápida, mais longo o rastro. A bola
+// parada não deixa rastro
+pub fn trail_lifetime(speed: f32) -> f32 {
+    if speed <= 0. {
+        return 0.;
+    }
+    (TRAIL_TIME * speed / TRAIL_SPEED).clamp(MIN_TRAIL_TIME, MAX_TRAIL_TIME)
+}
+
+// Sistema que deixa um ponto do rastro onde a bola está desenhada
+pub fn spawn_trail(
+    mut commands: Commands,
+    assets: Res<EffectAssets>,
+    ball: Query<&Transform, With<Ball>>,
+    sample: Res<BallSample>,
+    geometry: Res<MatchGeometry>,
+) {
+    let Ok(transform) = ball.get_single() else {
+        return;
+    };
+    let lifetime = trail_lifetime(sample.velocity.length());
+    if lifetime <= 0. {
+        return; // Bola parada, esperando o saque
+    }
+
+    let size = Vec2::splat(geometry.ball_radius);
+    commands.spawn((
+        TrailDot,
+        Fade::new(lifetime, 0.5, EffectColor::Ball, size, true),
+        Mesh2d(assets.circle.clone()),
+        MeshMaterial2d(assets.material(EffectColor::Ball, 0.5)),
+        Transform::from_translation(transform.translation.with_z(-0.5)) // Atrás da bola
+            .with_scale(size.extend(1.)),
+        StateScoped(InGame),
+    ));
+}
+
+// Sistema que ilumina a raquete mais próxima de cada toque em uma raquete
+pub fn flash_paddles(
+    mut commands: Commands,
+    assets: Res<EffectAssets>,
+    mut hits: EventReader<BallHit>,
+    paddles: Query<(Entity, &Transform), With<Paddle>>,
+    geometry: Res<MatchGeometry>,
+) {
+    for hit in hits.read().filter(|hit| hit.kind == HitKind::Paddle) {
+        let nearest = paddles.iter().min_by(|(_, a), (_, b)| {
+            let a = a.translation.truncate().distance_squared(hit.position);
+            let b = b.translation.truncate().distance_squared(hit.position);
+            a.total_cmp(&b)
+        });
+        if let Some((paddle, _)) = nearest {
+            let flash = commands
+                .spawn((
+                    PaddleFlash,
+                    Fade::new(
+                        FLASH_TIME,
+                        0.8,
+                        EffectColor::White,
+                        geometry.paddle_size,
+                        false,
+                    ),
+                    Mesh2d(assets.square.clone()),
+                    MeshMaterial2d(assets.material(EffectColor::White, 0.8)),
+                    Transform::from_xyz(0., 0., 0.1) // Sobre a raquete
+                        .with_scale(geometry.paddle_size.extend(1.)),
+                ))
+                .id();
+            commands.entity(paddle).add_child(flash);
+        }
+    }
+}
+
+// Sistema que solta partículas nos pontos de contato. O sorteio não usa o `GameRng`, para que
+// os efeitos não mudem a partida gerada por uma semente
+pub fn spawn_particles(
+    mut commands: Commands,
+    assets: Res<EffectAssets>,
+    mut hits: EventReader<BallHit>,
+    geometry: Res<MatchGeometry>,
+) {
+    let mut rng = rand::rng();
+    let size = Vec2::splat(geometry.ball_radius / 2.);
+    for hit in hits.read() {
+        for _ in 0..PARTICLES_PER_HIT {
+            let direction = Vec2::from_angle(rng.random_range(0. ..TAU));
+            let speed = rng.random_range(PARTICLE_SPEED / 3. ..PARTICLE_SPEED);
+            commands.spawn((
+                Particle(direction * speed),
+                Fade::new(PARTICLE_TIME, 1., EffectColor::White, size, true),
+                Mesh2d(assets.square.clone()),
+                MeshMaterial2d(assets.material(EffectColor::White, 1.)),
+                Transform::from_translation(hit.position.extend(0.5)) // Sobre a bola
+                    .with_scale(size.extend(1.)),
+                StateScoped(InGame),
+            ));
+        }
+    }
+}
+
+// Sistema que move as partículas
+pub fn move_particles(mut particles: Query<(&Particle, &mut Transform)>, time: Res<Time>) {
+    for (particle, mut transform) in &mut particles {
+        transform.translation += (particle.0 * time.delta_secs()).extend(0.);
+    }
+}
+
+// Sistema que apaga os efeitos aos poucos, trocando o material pelo nível de transparência
+// seguinte, e os remove ao fim do tempo
+pub fn fade_effects(
+    mut commands: Commands,
+    mut effects: Query<(
+        Entity,
+        &mut Fade,
+        &mut MeshMaterial2d<ColorMaterial>,
+        &mut Transform,
+    )>,
+    assets: Res<EffectAssets>,
+    time: Res<Time>,
+) {
+    for (entity, mut fade, mut material, mut transform) in &mut effects {
+        if fade.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let remaining = fade.timer.fraction_remaining();
+        let faded = assets.material(fade.color, fade.alpha * remaining);
+        if material.0 != faded {
+            material.0 = faded;
+        }
+        if fade.shrink {
+            transform.scale = fade.size * remaining;
+        }
+    }
+}
+
+// Sistema que começa o tremor da tela a cada ponto
+pub fn start_screen_shake(mut commands: Commands, mut scored: EventReader<Scored>) {
+    if scored.read().count() > 0 {
+        commands.insert_resource(ScreenShake(Timer::from_seconds(
+            SHAKE_TIME,
+            TimerMode::Once,
+        )));
+    }
+}
+
+// Sistema que desloca a câmera enquanto o tremor dura, cada vez menos, e a devolve ao centro
+pub fn shake_camera(
+    mut commands: Commands,
+    shake: Option<ResMut<ScreenShake>>,
+    mut cameras: Query<&mut Transform, With<Camera2d>>,
+    time: Res<Time>,
+) {
+    let Some(mut shake) = shake else {
+        return;
+    };
+
+    let offset = if shake.0.tick(time.delta()).finished() {
+        commands.remove_resource::<ScreenShake>();
+        Vec2::ZERO
+    } else {
+        let mut rng = rand::rng();
+        let strength = SHAKE_STRENGTH * shake.0.fraction_remaining();
+        Vec2::new(
+            rng.random_range(-strength..strength),
+            rng.random_range(-strength..strength),
+        )
+    };
+    for mut transform in &mut cameras {
+        transform.translation = offset.extend(transform.translation.z);
+    }
+}
+
+// Plugin com os efeitos visuais: rastro da bola, brilho da raquete atingida, partículas nos
+// toques e tremor da tela a cada ponto, cada um ligado em `EffectSettings`. Usa os eventos
+// `BallHit` e `Scored` e o `BallSample`, então funciona com qualquer versão
+pub struct EffectsPlugin;
+
+impl Plugin for EffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EffectSettings>() // Mantém os efeitos escolhidos pelo binário, se houver
+            .add_systems(Startup, load_effect_assets)
+            .add_systems(
+                Update,
+                update_trail_color.run_if(resource_changed::<GameConfig>),
+            )
+            .add_systems(
+                Update,
+                (
+                    spawn_trail
+                        .run_if(in_state(GameState::Playing)) // Só com a bola em jogo
+                        .run_if(|settings: Res<EffectSettings>| settings.trail),
+                    flash_paddles.run_if(|settings: Res<EffectSettings>| settings.flashes),
+                    spawn_particles.run_if(|settings: Res<EffectSettings>| settings.particles),
+                    start_screen_shake
+                        .run_if(|settings: Res<EffectSettings>| settings.screen_shake),
+                    move_particles,
+                    fade_effects,
+                )
+                    .run_if(in_state(InGame)),
+            )
+            .add_systems(Update, shake_camera); // Também devolve a câmera se a partida acabar
+    }
+}
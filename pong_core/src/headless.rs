@@ -0,0 +1,58 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, scene::ScenePlugin, state::app::StatesPlugin, time::TimeUpdateStrategy};
+
+use crate::GameState;
+
+// Plugin que substitui o `DefaultPlugins` para rodar a lógica do jogo sem janela nem renderização.
+// Cada `App::update` avança exatamente um passo fixo, tornando a simulação determinística.
+pub struct HeadlessPlugin {
+    pub timestep: Duration, // Duração de cada passo da simulação
+}
+
+impl Default for HeadlessPlugin {
+    fn default() -> Self {
+        Self {
+            timestep: Time::<Fixed>::default().timestep(),
+        }
+    }
+}
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            MinimalPlugins,
+            TransformPlugin,
+            HierarchyPlugin,
+            AssetPlugin::default(),
+            ScenePlugin, // Necessário para a física do avian
+            StatesPlugin,
+        ))
+        // Os sistemas de criação usam meshes e materiais mesmo sem renderização
+        .init_asset::<Mesh>()
+        .init_asset::<ColorMaterial>()
+        .insert_resource(Time::<Fixed>::from_duration(self.timestep))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(self.timestep));
+    }
+}
+
+// Avança a simulação pelo número de passos indicado
+pub fn step(app: &mut App, steps: u32) {
+    for _ in 0..steps {
+        app.update();
+    }
+}
+
+// Roda a partida sem janela até o fim de jogo ou até `max_steps` passos, o que vier antes, e
+// devolve quantos passos foram simulados
+pub fn run_simulation(app: &mut App, max_steps: Option<u32>) -> u32 {
+    let mut steps = 0;
+    while max_steps.is_none_or(|max| steps < max) {
+        app.update();
+        steps += 1;
+        if *app.world().resource::<State<GameState>>().get() == GameState::GameOver {
+            break;
+        }
+    }
+    steps
+}
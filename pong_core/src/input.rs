@@ -0,0 +1,259 @@
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Ações do jogo, independentes do dispositivo usado
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Serialize, Deserialize)]
+pub enum Action {
+    MoveUp,   // Move a raquete para cima
+    MoveDown, // Move a raquete para baixo
+    Pause,    // Pausa ou retoma o jogo
+    Serve,    // Saca a bola
+}
+
+const ACTIONS: [Action; 4] = [
+    Action::MoveUp,
+    Action::MoveDown,
+    Action::Pause,
+    Action::Serve,
+];
+
+// Sentido de um eixo analógico que aciona uma ação
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize)]
+pub enum AxisDirection {
+    Positive,
+    Negative,
+}
+
+// Entrada física ligada a uma ação
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub enum Binding {
+    Key(KeyCode),                     // Tecla do teclado
+    Button(GamepadButton),            // Botão do controle, incluindo o direcional
+    Axis(GamepadAxis, AxisDirection), // Eixo analógico do controle, proporcional à inclinação
+}
+
+// Controles de um jogador
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct PlayerBindings {
+    pub actions: BTreeMap<Action, Vec<Binding>>, // Entradas de cada ação
+    pub gamepad: Option<usize>, // Índice do controle usado, na ordem em que foram conectados
+    pub dead_zone: f32,         // Inclinação mínima dos eixos analógicos, de 0 a 1
+}
+
+impl PlayerBindings {
+    // Controles padrão: as teclas indicadas para cima e para baixo, mais o controle indicado
+    pub fn new(up: KeyCode, down: KeyCode, gamepad: usize) -> Self {
+        let actions = BTreeMap::from([
+            (
+                Action::MoveUp,
+                vec![
+                    Binding::Key(up),
+                    Binding::Button(GamepadButton::DPadUp),
+                    Binding::Axis(GamepadAxis::LeftStickY, AxisDirection::Positive),
+                ],
+            ),
+            (
+                Action::MoveDown,
+                vec![
+                    Binding::Key(down),
+                    Binding::Button(GamepadButton::DPadDown),
+                    Binding::Axis(GamepadAxis::LeftStickY, AxisDirection::Negative),
+                ],
+            ),
+            (
+                Action::Pause,
+                vec![
+                    Binding::Key(KeyCode::Escape),
+                    Binding::Button(GamepadButton::Start),
+                ],
+            ),
+            (
+                Action::Serve,
+                vec![
+                    Binding::Key(KeyCode::Space),
+                    Binding::Button(GamepadButton::South),
+                ],
+            ),
+        ]);
+
+        Self {
+            actions,
+            gamepad: Some(gamepad),
+            dead_zone: 0.2,
+        }
+    }
+
+    // Quanto a ação está acionada, de 0 a 1; teclas e botões valem 0 ou 1
+    fn strength(
+        &self,
+        action: Action,
+        keyboard: &ButtonInput<KeyCode>,
+        gamepad: Option<&Gamepad>,
+    ) -> f32 {
+        let bindings = self.actions.get(&action).into_iter().flatten();
+        bindings
+            .map(|binding| match (*binding, gamepad) {
+                (Binding::Key(key), _) => f32::from(u8::from(keyboard.pressed(key))),
+                (Binding::Button(button), Some(gamepad)) => {
+                    f32::from(u8::from(gamepad.pressed(button)))
+                }
+                (Binding::Axis(axis, direction), Some(gamepad)) => {
+                    let value = gamepad.get(axis).unwrap_or(0.);
+                    let value = match direction {
+                        AxisDirection::Positive => value,
+                        AxisDirection::Negative => -value,
+                    };
+                    // Reescala para que a ação comece em 0 logo após a zona morta
+                    ((value - self.dead_zone) / (1. - self.dead_zone)).clamp(0., 1.)
+                }
+                (_, None) => 0.,
+            })
+            .fold(0., f32::max)
+    }
+
+    // Se a ação acabou de ser acionada neste quadro
+    fn just_pressed(
+        &self,
+        action: Action,
+        keyboard: &ButtonInput<KeyCode>,
+        gamepad: Option<&Gamepad>,
+    ) -> bool {
+        let bindings = self.actions.get(&action).into_iter().flatten();
+        bindings
+            .into_iter()
+            .any(|binding| match (*binding, gamepad) {
+                (Binding::Key(key), _) => keyboard.just_pressed(key),
+                (Binding::Button(button), Some(gamepad)) => gamepad.just_pressed(button),
+                _ => false,
+            })
+    }
+}
+
+// Recurso com os controles de cada jogador: o primeiro é o da direita e o segundo o da esquerda
+#[derive(Resource, Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct InputMap {
+    pub players: Vec<PlayerBindings>,
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        Self {
+            players: vec![
+                PlayerBindings::new(KeyCode::ArrowUp, KeyCode::ArrowDown, 0),
+                PlayerBindings::new(KeyCode::KeyW, KeyCode::KeyS, 1),
+            ],
+        }
+    }
+}
+
+impl InputMap {
+    // Lê os controles de um arquivo RON
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        ron::from_str(&text).map_err(io::Error::other)
+    }
+
+    // Grava os controles em um arquivo RON, legível para edição manual
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let text = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(io::Error::other)?;
+        fs::write(path, text)
+    }
+
+    // Lê os controles do arquivo; se ele não existir, grava os controles padrão para servir de
+    // modelo. Um arquivo inválido é avisado e ignorado. Roda antes do `App` existir, por isso
+    // os avisos vão direto para a saída de erro.
+    pub fn load_or_create(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        match Self::load(path) {
+            Ok(map) => map,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                let map = Self::default();
+                if let Err(error) = map.save(path) {
+                    eprintln!("Não foi possível gravar {}: {error}", path.display());
+                }
+                map
+            }
+            Err(error) => {
+                eprintln!("Controles inválidos em {}: {error}", path.display());
+                Self::default()
+            }
+        }
+    }
+}
+
+// Estado das ações de um jogador no passo atual
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionState {
+    pub movement: f32, // Movimento da raquete, de -1 (para baixo) a 1 (para cima)
+    just_pressed: Vec<Action>,
+}
+
+impl ActionState {
+    // Se a ação acabou de ser acionada
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.just_pressed.contains(&action)
+    }
+
+    // Ações que acabaram de ser acionadas
+    pub fn pressed(&self) -> &[Action] {
+        &self.just_pressed
+    }
+
+    // Marca uma ação como acabada de acionar
+    pub fn press(&mut self, action: Action) {
+        if !self.just_pressed(action) {
+            self.just_pressed.push(action);
+        }
+    }
+}
+
+// Recurso com o estado das ações de cada jogador, na mesma ordem de `InputMap::players`
+#[derive(Resource, Debug, Default)]
+pub struct PlayerActions(pub Vec<ActionState>);
+
+impl PlayerActions {
+    // Estado das ações de um jogador; vazio se ele não tiver controles
+    pub fn get(&self, player: usize) -> ActionState {
+        self.0.get(player).cloned().unwrap_or_default()
+    }
+}
+
+// Componente que liga uma raquete aos controles de um jogador do `InputMap`
+#[derive(Component, Debug, Clone, Copy)]
+pub struct PlayerSlot(pub usize);
+
+// Sistema que traduz teclado e controles para as ações de cada jogador
+pub fn update_player_actions(
+    input_map: Res<InputMap>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    mut actions: ResMut<PlayerActions>,
+) {
+    // Ordena os controles pela entidade para que os índices não mudem entre quadros
+    let mut gamepads: Vec<_> = gamepads.iter().collect();
+    gamepads.sort_by_key(|(entity, _)| *entity);
+
+    actions.0 = input_map
+        .players
+        .iter()
+        .map(|bindings| {
+            let gamepad = bindings
+                .gamepad
+                .and_then(|index| gamepads.get(index))
+                .map(|(_, gamepad)| *gamepad);
+            let up = bindings.strength(Action::MoveUp, &keyboard, gamepad);
+            let down = bindings.strength(Action::MoveDown, &keyboard, gamepad);
+
+            ActionState {
+                movement: up - down,
+                just_pressed: ACTIONS
+                    .into_iter()
+                    .filter(|action| bindings.just_pressed(*action, &keyboard, gamepad))
+                    .collect(),
+            }
+        })
+        .collect();
+}
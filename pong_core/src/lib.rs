@@ -0,0 +1,309 @@
+use bevy::{input::InputSystem, prelude::*, time::TimeSystem};
+use serde::{Deserialize, Serialize};
+
+mod ai;
+mod arena;
+mod audio;
+mod bounce;
+mod cli;
+mod components;
+mod config;
+mod display;
+mod effects;
+mod headless;
+mod input;
+mod replay;
+mod rng;
+mod rules;
+mod scoring;
+mod spawn;
+mod spectator;
+mod speed;
+mod state;
+
+pub use ai::{add_ai_controller, predict_intercept, AiController, AiSettings, AiView, Difficulty};
+pub use arena::{fit_camera_to_window, letterbox_viewport, Arena, ARENA_HEIGHT, ARENA_WIDTH};
+pub use audio::{
+    load_sound_effects, play_serve_sound, play_sound_effects, BallHit, HitKind, SoundEffects,
+    SoundSettings,
+};
+pub use bounce::paddle_bounce;
+pub use cli::{run_app, CliOptions, USAGE};
+pub use components::{Ai, Ball, BallSample, GameMode, Paddle, Player};
+pub use config::{
+    reload_config, store_match_geometry, ConfigFile, MatchGeometry, CONFIG_POLL_INTERVAL,
+};
+pub use display::{apply_display_settings, choose_monitor, DisplaySettings, WindowChoice};
+pub use effects::{
+    fade_effects, flash_paddles, load_effect_assets, move_particles, shake_camera, spawn_particles,
+    spawn_trail, start_screen_shake, trail_lifetime, update_trail_color, EffectAssets, EffectColor,
+    EffectSettings, EffectsPlugin, Fade, PaddleFlash, Particle, ScreenShake, TrailDot, FADE_LEVELS,
+    FLASH_TIME, MAX_TRAIL_TIME, MIN_TRAIL_TIME, PARTICLES_PER_HIT, PARTICLE_SPEED, PARTICLE_TIME,
+    SHAKE_STRENGTH, SHAKE_TIME, TRAIL_SPEED, TRAIL_TIME,
+};
+pub use headless::{run_simulation, step, HeadlessPlugin};
+pub use input::{
+    update_player_actions, Action, ActionState, AxisDirection, Binding, InputMap, PlayerActions,
+    PlayerBindings, PlayerSlot,
+};
+pub use replay::{
+    ball_checksum, feed_replay_actions, feed_replay_movement, record_frame, record_tick,
+    save_recording, set_replay_frame_time, start_recording, start_replay, track_ball_checksum,
+    Replay, ReplayPlayer, ReplayRecorder,
+};
+pub use rng::{log_seed, GameRng};
+pub use rules::{
+    apply_match_rules, random_serve_direction, MatchRules, MatchWon, ServeRule, Server,
+    MAX_SERVE_ANGLE, MIN_SERVE_ANGLE,
+};
+pub use scoring::{
+    spawn_scoreboard, update_score, update_scoreboard, LeftScore, RightScore, Score, Scored, Scorer,
+};
+pub use spawn::{spawn_ball, spawn_camera, spawn_paddles};
+pub use spectator::{
+    broadcast_to_spectators, SpectatorClient, SpectatorFrame, SpectatorServer,
+    SPECTATOR_FRAME_SIZE, SPECTATOR_RATE,
+};
+pub use speed::SpeedCurve;
+pub use state::{
+    choose_difficulty, handle_state_input, paddles_can_move, reset_match, serve_after_point,
+    spawn_game_over_screen, spawn_main_menu, spawn_pause_screen, spawn_serve_hint,
+    start_serve_countdown, tick_serve_countdown, CountdownText, DifficultyText, GameState, InGame,
+    OnlineMatch, ServeCountdown,
+};
+
+// Valores padrão para os tamanhos e velocidades dos elementos do jogo
+pub const BALL_SIZE: f32 = 5.; // Raio da bola
+pub const BALL_SPEED: f32 = 60.; // Velocidade base da bola, em unidades por segundo
+pub const SPEED_INCREMENT: f32 = 0.; // Aumento da velocidade da bola a cada toque de raquete
+pub const PADDLE_SPEED: f32 = 240.; // Velocidade das raquetes, em unidades por segundo
+pub const WALL_THICKNESS: f32 = 96.; // Espessura das paredes superior e inferior
+pub const PADDLE_WIDTH: f32 = 10.; // Largura das raquetes
+pub const PADDLE_HEIGHT: f32 = 50.; // Altura das raquetes
+pub const PADDLE_PADDING: f32 = 50.; // Distância das raquetes até a borda da arena
+pub const MAX_BALL_SPEED: f32 = 4000.; // Velocidade máxima da bola, em unidades por segundo
+pub const MAX_BOUNCE_ANGLE: f32 = std::f32::consts::FRAC_PI_3; // Desvio máximo da bola ao sair de uma raquete (60°)
+pub const PADDLE_SPIN: f32 = 0.25; // Fração da velocidade vertical da raquete passada para a bola
+pub const SERVE_DELAY: f32 = 3.; // Contagem regressiva antes de cada saque, em segundos
+pub const MIN_HORIZONTAL_SPEED: f32 = 150.; // Velocidade horizontal mínima da bola após um toque
+pub const MAX_BALL_ANGLE: f32 = 5. * std::f32::consts::PI / 12.; // Inclinação máxima da bola após um toque (75°)
+pub const STALL_TIME: f32 = 15.; // Segundos sem toque de raquete até a bola ser sacada de novo
+
+// Recurso com as dimensões, velocidades e cores do jogo. Os binários o leem de um arquivo RON
+// e o recarregam quando o arquivo muda (veja `ConfigFile`)
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameConfig {
+    pub ball_radius: f32,      // Raio da bola
+    pub ball_color: Color,     // Cor da bola
+    pub ball_speed: f32,       // Velocidade base da bola, em unidades por segundo
+    pub speed_increment: f32,  // Aumento da velocidade da bola a cada toque de raquete
+    pub paddle_speed: f32,     // Velocidade das raquetes, em unidades por segundo
+    pub wall_thickness: f32,   // Espessura das paredes superior e inferior
+    pub paddle_width: f32,     // Largura das raquetes
+    pub paddle_height: f32,    // Altura das raquetes
+    pub paddle_padding: f32,   // Distância das raquetes até a borda da arena
+    pub max_ball_speed: f32,   // Velocidade máxima da bola, até onde a colisão contínua é garantida
+    pub max_bounce_angle: f32, // Desvio máximo da bola ao sair de uma raquete, em radianos
+    pub paddle_spin: f32, // Fração da velocidade vertical da raquete passada para a bola (0 desliga)
+    pub serve_delay: f32, // Contagem regressiva antes de cada saque, em segundos
+    // Ajustes da aceleração e do rali têm valor padrão para que arquivos anteriores a eles
+    // continuem válidos
+    #[serde(default)]
+    pub speed_curve: SpeedCurve, // Como a velocidade cresce a cada toque de raquete
+    #[serde(default = "reset_speed_on_score")]
+    pub reset_speed_on_score: bool, // Se a bola volta à velocidade inicial após cada ponto
+    #[serde(default = "min_horizontal_speed")]
+    pub min_horizontal_speed: f32, // Velocidade horizontal mínima da bola após um toque (0 desliga)
+    #[serde(default = "max_ball_angle")]
+    pub max_ball_angle: f32, // Inclinação máxima da bola após um toque, em radianos
+    #[serde(default = "stall_time")]
+    pub stall_time: f32, // Segundos sem toque de raquete até a bola ser sacada de novo
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            ball_radius: BALL_SIZE,
+            ball_color: Color::srgb(1., 0., 0.), // Vermelho
+            ball_speed: BALL_SPEED,
+            speed_increment: SPEED_INCREMENT,
+            paddle_speed: PADDLE_SPEED,
+            wall_thickness: WALL_THICKNESS,
+            paddle_width: PADDLE_WIDTH,
+            paddle_height: PADDLE_HEIGHT,
+            paddle_padding: PADDLE_PADDING,
+            max_ball_speed: MAX_BALL_SPEED,
+            max_bounce_angle: MAX_BOUNCE_ANGLE,
+            paddle_spin: PADDLE_SPIN,
+            serve_delay: SERVE_DELAY,
+            speed_curve: SpeedCurve::Linear,
+            reset_speed_on_score: reset_speed_on_score(),
+            min_horizontal_speed: MIN_HORIZONTAL_SPEED,
+            max_ball_angle: MAX_BALL_ANGLE,
+            stall_time: STALL_TIME,
+        }
+    }
+}
+
+fn reset_speed_on_score() -> bool {
+    true
+}
+
+fn min_horizontal_speed() -> f32 {
+    MIN_HORIZONTAL_SPEED
+}
+
+fn max_ball_angle() -> f32 {
+    MAX_BALL_ANGLE
+}
+
+fn stall_time() -> f32 {
+    STALL_TIME
+}
+
+impl GameConfig {
+    // Tamanho das raquetes como vetor
+    pub fn paddle_size(&self) -> Vec2 {
+        Vec2::new(self.paddle_width, self.paddle_height)
+    }
+}
+
+// Plugin com a base comum a todas as versões: configuração, arena, câmera, bola, placar,
+// controles, IA, gerador aleatório, efeitos sonoros e visuais, gravação e transmissão de partidas e os
+// estados do jogo. Cada versão adiciona as próprias regras (física, detecção de pontos, entrada)
+// por cima dele, envia `Scored` quando a bola passa por uma das raquetes e `BallHit` a cada
+// reflexão. As entidades da partida são criadas em `OnEnter(InGame)` com `StateScoped(InGame)`
+// e as regras rodam só nos estados em que a bola ou as raquetes se movem.
+pub struct PongPlugin;
+
+impl Plugin for PongPlugin {
+    fn build(&self, app: &mut App) {
+        // Mantém o estado inicial escolhido antes do plugin, como nos testes que começam jogando
+        if !app.world().contains_resource::<State<GameState>>() {
+            app.init_state::<GameState>();
+        }
+
+        app.add_plugins(EffectsPlugin) // Rastro, brilhos, partículas e tremor da tela
+            .add_computed_state::<InGame>()
+            .enable_state_scoped_entities::<GameState>()
+            .enable_state_scoped_entities::<InGame>()
+            .init_resource::<GameConfig>() // Mantém a configuração inserida pela versão, se houver
+            .init_resource::<Arena>()
+            .init_resource::<MatchGeometry>() // Medidas fixas de cada partida
+            .init_resource::<Score>() // Inicializa o recurso de pontuação
+            .init_resource::<MatchRules>() // Mantém as regras escolhidas pela versão, se houver
+            .init_resource::<Server>()
+            .init_resource::<GameRng>() // Mantém a semente escolhida pelo binário, se houver
+            .init_resource::<GameMode>() // Mantém o modo escolhido pela versão, se houver
+            .init_resource::<AiSettings>() // Mantém a dificuldade escolhida pela versão, se houver
+            .init_resource::<Difficulty>() // Nível mostrado e trocado no menu inicial
+            .init_resource::<InputMap>() // Mantém os controles lidos pelo binário, se houver
+            .init_resource::<PlayerActions>()
+            .init_resource::<BallSample>()
+            .init_resource::<SoundSettings>() // Mantém o volume escolhido pelo binário, se houver
+            .init_resource::<ButtonInput<KeyCode>>() // Garante o recurso de teclado mesmo sem o `InputPlugin`
+            .add_observer(add_ai_controller) // Controlador para as raquetes da IA
+            .add_event::<Scored>() // Adiciona o evento de pontuação
+            .add_event::<MatchWon>() // Evento de fim de partida
+            .add_event::<BallHit>() // Reflexões da bola, para os efeitos sonoros
+            .add_systems(Startup, (spawn_camera, log_seed, load_sound_effects))
+            .add_systems(
+                OnEnter(InGame),
+                (
+                    store_match_geometry,
+                    reset_match,
+                    spawn_ball.after(store_match_geometry),
+                    spawn_scoreboard,
+                ),
+            ) // Nova partida
+            .add_systems(OnEnter(GameState::MainMenu), spawn_main_menu)
+            .add_systems(
+                OnEnter(GameState::Serving),
+                (start_serve_countdown, spawn_serve_hint),
+            )
+            .add_systems(OnEnter(GameState::Paused), spawn_pause_screen)
+            .add_systems(OnEnter(GameState::GameOver), spawn_game_over_screen)
+            .add_systems(PreUpdate, update_player_actions.after(InputSystem)) // Ações de cada jogador
+            // Gravação e repetição de partidas, ativas só com o recurso correspondente
+            .add_systems(
+                Startup,
+                start_recording.run_if(resource_exists::<ReplayRecorder>),
+            )
+            .add_systems(
+                First,
+                set_replay_frame_time
+                    .before(TimeSystem)
+                    .run_if(resource_exists::<ReplayPlayer>),
+            )
+            .add_systems(
+                PreUpdate,
+                feed_replay_actions
+                    .after(update_player_actions)
+                    .run_if(resource_exists::<ReplayPlayer>),
+            )
+            .add_systems(
+                FixedFirst,
+                (
+                    record_tick.run_if(resource_exists::<ReplayRecorder>),
+                    feed_replay_movement.run_if(resource_exists::<ReplayPlayer>),
+                ),
+            )
+            .add_systems(
+                Last,
+                (record_frame, save_recording)
+                    .chain()
+                    .run_if(resource_exists::<ReplayRecorder>),
+            )
+            // Recarrega a configuração quando o arquivo muda; fica fixa durante a gravação e a
+            // repetição de partidas, que dependem dela para se repetir igual
+            .add_systems(
+                Update,
+                reload_config
+                    .run_if(resource_exists::<ConfigFile>)
+                    .run_if(not(resource_exists::<ReplayRecorder>))
+                    .run_if(not(resource_exists::<ReplayPlayer>)),
+            )
+            .add_systems(
+                Update,
+                apply_display_settings.run_if(resource_exists::<DisplaySettings>), // Janela pedida pelo binário
+            )
+            .add_systems(FixedLast, track_ball_checksum) // Depois que a versão preencheu `BallSample`
+            .add_systems(
+                FixedUpdate,
+                // Saca ao fim da contagem; conta passos fixos para que a repetição saque no mesmo passo
+                tick_serve_countdown.run_if(in_state(GameState::Serving)),
+            )
+            .add_systems(
+                Update,
+                broadcast_to_spectators.run_if(resource_exists::<SpectatorServer>), // Transmissão da partida
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Serving,
+                    entered: GameState::Playing,
+                },
+                play_serve_sound.run_if(resource_exists::<SoundEffects>), // Som do saque
+            )
+            .add_systems(
+                Update,
+                play_sound_effects
+                    .after(apply_match_rules)
+                    .run_if(resource_exists::<SoundEffects>), // Efeitos sonoros, só com áudio
+            )
+            .add_systems(
+                Update,
+                (
+                    update_score,                                                   // Atualiza a pontuação
+                    update_scoreboard.after(apply_match_rules), // Atualiza o placar visual
+                    fit_camera_to_window, // Mantém a arena inteira visível em qualquer janela
+                    handle_state_input.run_if(not(resource_exists::<OnlineMatch>)), // Saque e pausa
+                    choose_difficulty.run_if(in_state(GameState::MainMenu)), // Dificuldade da IA no menu
+                    serve_after_point.run_if(in_state(GameState::Playing)), // Volta para o saque após cada ponto
+                    // Encerra sets e a partida; roda por último para que o fim de jogo prevaleça sobre o saque
+                    apply_match_rules
+                        .after(update_score)
+                        .after(serve_after_point),
+                ),
+            );
+    }
+}
@@ -0,0 +1,308 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Action, ActionState, AiSettings, BallSample, Difficulty, GameConfig, GameMode, GameRng,
+    PlayerActions,
+};
+
+// Gravação de uma partida: a semente, o modo, a dificuldade e os ajustes da IA, a configuração,
+// o movimento dos jogadores em cada passo fixo e, para cada quadro, quantos passos fixos ele
+// rodou e as ações acionadas nele (saque, pausa, escolha no menu). As trocas de estado só
+// acontecem entre quadros, então repetir quantos passos cabem em cada quadro faz elas caírem
+// nos mesmos passos da partida original, seja qual for a duração dos quadros. Ao fim de cada
+// passo fixo é guardado um resumo do estado da bola para detectar onde a repetição se afasta
+// da partida original.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    pub seed: u64,                          // Semente do `GameRng`
+    pub mode: GameMode,                     // Quem controla a raquete da esquerda
+    pub difficulty: Difficulty,             // Dificuldade no início, que o menu pode trocar
+    pub ai: AiSettings,                     // Ajustes da IA
+    pub config: GameConfig,                 // Tamanhos e velocidades da partida
+    pub frames: Vec<(u32, u32)>, // Passos fixos de cada quadro e por quantos quadros seguidos o número se repete
+    pub presses: Vec<(u32, usize, Action)>, // Ações acionadas: quadro, jogador e ação
+    pub movement: Vec<(u32, Vec<f32>)>, // Movimento de cada jogador e por quantos passos fixos seguidos ele se repete
+    pub checksums: Vec<u32>,            // Resumo do estado da bola ao fim de cada passo fixo
+}
+
+impl Replay {
+    // Lê uma gravação de um arquivo RON
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        ron::from_str(&text).map_err(io::Error::other)
+    }
+
+    // Grava a partida em um arquivo RON compacto
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let text = ron::to_string(self).map_err(io::Error::other)?;
+        fs::write(path, text)
+    }
+
+    // Quantidade de quadros gravados
+    pub fn frame_count(&self) -> usize {
+        self.frames.iter().map(|(count, _)| *count as usize).sum()
+    }
+
+    // Acrescenta um quadro que rodou `ticks` passos fixos, juntando quadros seguidos iguais
+    pub fn push_frame(&mut self, ticks: u32) {
+        match self.frames.last_mut() {
+            Some((count, last)) if *last == ticks => *count += 1,
+            _ => self.frames.push((1, ticks)),
+        }
+    }
+
+    // Guarda as ações que os jogadores acionaram no quadro `frame`
+    pub fn push_presses(&mut self, frame: u32, actions: &[ActionState]) {
+        for (player, state) in actions.iter().enumerate() {
+            let presses = state
+                .pressed()
+                .iter()
+                .map(|action| (frame, player, *action));
+            self.presses.extend(presses);
+        }
+    }
+
+    // Acrescenta o movimento dos jogadores em um passo fixo, juntando passos seguidos iguais
+    pub fn push_tick(&mut self, actions: &[ActionState]) {
+        let movement: Vec<f32> = actions.iter().map(|state| state.movement).collect();
+        match self.movement.last_mut() {
+            Some((count, last)) if *last == movement => *count += 1,
+            _ => self.movement.push((1, movement)),
+        }
+    }
+}
+
+// Resumo do estado da bola em um passo: FNV-1a sobre os bits exatos da posição e da
+// velocidade, então qualquer diferença, por menor que seja, muda o resultado
+pub fn ball_checksum(position: Vec2, velocity: Vec2) -> u32 {
+    [position.x, position.y, velocity.x, velocity.y]
+        .iter()
+        .flat_map(|value| value.to_bits().to_le_bytes())
+        .fold(0x811c_9dc5, |hash, byte| {
+            (hash ^ byte as u32).wrapping_mul(0x0100_0193)
+        })
+}
+
+// Recurso que grava a partida; inserido pelo binário, que escolhe o arquivo de destino. A
+// gravação é salva quando o jogo fecha
+#[derive(Resource)]
+pub struct ReplayRecorder {
+    path: PathBuf,      // Arquivo de destino
+    pub replay: Replay, // Partida gravada até agora
+    frame: u32,         // Quadro atual
+    ticks: u32,         // Passos fixos já rodados no quadro atual
+}
+
+impl ReplayRecorder {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            replay: Replay::default(),
+            frame: 0,
+            ticks: 0,
+        }
+    }
+}
+
+// Recurso que repete uma gravação no lugar das ações dos jogadores
+#[derive(Resource)]
+pub struct ReplayPlayer {
+    frames: Vec<u32>,                   // Passos fixos de cada quadro
+    presses: Vec<(u32, usize, Action)>, // Ações acionadas: quadro, jogador e ação
+    movement: Vec<Vec<f32>>,            // Movimento dos jogadores em cada passo fixo
+    checksums: Vec<u32>,                // Resumo esperado da bola em cada passo fixo
+    players: usize,                     // Quantidade de jogadores gravados
+    frame: usize,                       // Próximo quadro a repetir
+    press: usize,                       // Próxima ação acionada a repetir
+    tick: usize,                        // Passo fixo atual
+    diverged: Option<usize>,            // Primeiro passo em que a bola se afastou da gravação
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: Replay) -> Self {
+        let frames = replay
+            .frames
+            .into_iter()
+            .flat_map(|(count, ticks)| std::iter::repeat_n(ticks, count as usize))
+            .collect();
+        let players = replay
+            .movement
+            .first()
+            .map_or(0, |(_, movement)| movement.len());
+        let movement = replay
+            .movement
+            .into_iter()
+            .flat_map(|(count, movement)| std::iter::repeat_n(movement, count as usize))
+            .collect();
+        Self {
+            frames,
+            presses: replay.presses,
+            movement,
+            checksums: replay.checksums,
+            players,
+            frame: 0,
+            press: 0,
+            tick: 0,
+            diverged: None,
+        }
+    }
+
+    // Se todos os quadros gravados já foram repetidos
+    pub fn finished(&self) -> bool {
+        self.frame >= self.frames.len()
+    }
+
+    // Primeiro passo fixo em que a bola se afastou da gravação, se houver
+    pub fn diverged(&self) -> Option<usize> {
+        self.diverged
+    }
+
+    // Ações dos jogadores no quadro atual, com o movimento do próximo passo fixo
+    fn frame_actions(&mut self) -> Vec<ActionState> {
+        let mut actions = vec![ActionState::default(); self.players];
+        self.apply_movement(&mut actions);
+        while let Some(&(frame, player, action)) = self.presses.get(self.press) {
+            if frame as usize > self.frame {
+                break;
+            }
+            if frame as usize == self.frame {
+                if actions.len() <= player {
+                    actions.resize_with(player + 1, ActionState::default);
+                }
+                actions[player].press(action);
+            }
+            self.press += 1;
+        }
+        actions
+    }
+
+    // Troca o movimento dos jogadores pelo gravado para o passo fixo atual
+    fn apply_movement(&self, actions: &mut Vec<ActionState>) {
+        let Some(movement) = self.movement.get(self.tick) else {
+            return;
+        };
+        if actions.len() < movement.len() {
+            actions.resize_with(movement.len(), ActionState::default);
+        }
+        for (state, movement) in actions.iter_mut().zip(movement) {
+            state.movement = *movement;
+        }
+    }
+}
+
+// Prepara o `App` para repetir uma gravação com a mesma semente, modo, IA e configuração da
+// partida original
+pub fn start_replay(app: &mut App, replay: Replay) {
+    app.insert_resource(GameRng::new(replay.seed))
+        .insert_resource(replay.mode)
+        .insert_resource(replay.difficulty)
+        .insert_resource(replay.ai)
+        .insert_resource(replay.config.clone())
+        .insert_resource(ReplayPlayer::new(replay));
+}
+
+// Sistema que guarda no início da gravação o que define a partida
+pub fn start_recording(
+    mut recorder: ResMut<ReplayRecorder>,
+    rng: Res<GameRng>,
+    mode: Res<GameMode>,
+    difficulty: Res<Difficulty>,
+    settings: Res<AiSettings>,
+    config: Res<GameConfig>,
+) {
+    recorder.replay.seed = rng.seed();
+    recorder.replay.mode = *mode;
+    recorder.replay.difficulty = *difficulty;
+    recorder.replay.ai = *settings;
+    recorder.replay.config = config.clone();
+}
+
+// Sistema que grava o movimento dos jogadores no início de cada passo fixo
+pub fn record_tick(mut recorder: ResMut<ReplayRecorder>, actions: Res<PlayerActions>) {
+    recorder.replay.push_tick(&actions.0);
+    recorder.ticks += 1;
+}
+
+// Sistema que grava, ao fim de cada quadro, quantos passos fixos ele rodou e as ações acionadas
+pub fn record_frame(mut recorder: ResMut<ReplayRecorder>, actions: Res<PlayerActions>) {
+    let (frame, ticks) = (recorder.frame, recorder.ticks);
+    recorder.replay.push_presses(frame, &actions.0);
+    recorder.replay.push_frame(ticks);
+    recorder.frame += 1;
+    recorder.ticks = 0;
+}
+
+// Sistema que salva a gravação quando o jogo fecha
+pub fn save_recording(mut exits: EventReader<AppExit>, recorder: Res<ReplayRecorder>) {
+    if exits.read().next().is_none() {
+        return;
+    }
+    match recorder.replay.save(&recorder.path) {
+        Ok(()) => info!("Partida gravada em {}", recorder.path.display()),
+        Err(error) => error!(
+            "Não foi possível gravar {}: {error}",
+            recorder.path.display()
+        ),
+    }
+}
+
+// Sistema que faz o relógio avançar exatamente os passos fixos do quadro gravado; ao fim da
+// gravação o relógio volta ao normal e os controles voltam para os jogadores
+pub fn set_replay_frame_time(
+    player: Res<ReplayPlayer>,
+    fixed: Res<Time<Fixed>>,
+    mut strategy: ResMut<TimeUpdateStrategy>,
+) {
+    match player.frames.get(player.frame) {
+        Some(&ticks) => *strategy = TimeUpdateStrategy::ManualDuration(fixed.timestep() * ticks),
+        None if player.is_changed() => {
+            *strategy = TimeUpdateStrategy::Automatic;
+            info!("Fim da gravação");
+        }
+        None => {}
+    }
+}
+
+// Sistema que troca as ações dos jogadores pelas acionadas no quadro gravado
+pub fn feed_replay_actions(mut player: ResMut<ReplayPlayer>, mut actions: ResMut<PlayerActions>) {
+    if player.finished() {
+        return;
+    }
+    actions.0 = player.frame_actions();
+    player.frame += 1;
+}
+
+// Sistema que troca o movimento dos jogadores pelo gravado no início de cada passo fixo
+pub fn feed_replay_movement(player: Res<ReplayPlayer>, mut actions: ResMut<PlayerActions>) {
+    player.apply_movement(&mut actions.0);
+}
+
+// Sistema que resume a bola ao fim de cada passo fixo: grava o resumo durante a gravação e o
+// compara durante a repetição
+pub fn track_ball_checksum(
+    sample: Res<BallSample>,
+    recorder: Option<ResMut<ReplayRecorder>>,
+    player: Option<ResMut<ReplayPlayer>>,
+) {
+    let checksum = ball_checksum(sample.position, sample.velocity);
+
+    if let Some(mut recorder) = recorder {
+        recorder.replay.checksums.push(checksum);
+    }
+
+    if let Some(mut player) = player {
+        let tick = player.tick;
+        player.tick += 1;
+        let expected = player.checksums.get(tick).copied();
+        if player.diverged.is_none() && expected.is_some_and(|expected| expected != checksum) {
+            warn!("A repetição se afastou da gravação no passo {tick}");
+            player.diverged = Some(tick);
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+// Recurso com o gerador aleatório da partida. Todas as decisões aleatórias (ângulo do saque,
+// erro da IA) usam este gerador, então a mesma semente repete a partida exatamente, seja em
+// um relato de bug ou em um teste da simulação
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,   // Semente usada para criar o gerador
+    rng: StdRng, // Gerador propriamente dito
+}
+
+impl GameRng {
+    // Cria o gerador a partir de uma semente fixa
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    // Semente usada para criar o gerador, para repetir a partida
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+// Sem semente escolhida, sorteia uma; ela aparece no log para que a partida possa ser repetida
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::new(rand::random())
+    }
+}
+
+// Permite usar o recurso diretamente onde se espera um `Rng`
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest);
+    }
+}
+
+// Sistema que registra a semente da partida ao iniciar
+pub fn log_seed(rng: Res<GameRng>) {
+    info!("Semente aleatória: {}", rng.seed());
+}
@@ -0,0 +1,146 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{GameState, Score, Scored, Scorer};
+
+// Quem saca depois de cada ponto
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub enum ServeRule {
+    #[default]
+    Conceder, // Quem sofreu o ponto
+    Scorer,    // Quem marcou o ponto
+    Alternate, // Os lados se alternam a cada ponto
+}
+
+// Recurso com as regras da partida; inserido antes do `PongPlugin` para trocar os padrões
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct MatchRules {
+    pub points_to_win: u32,    // Pontos para vencer um set
+    pub win_by_two: bool,      // Se o set exige dois pontos de vantagem (vantagem após empate)
+    pub best_of: u32,          // Número máximo de sets; vence quem ganhar a maioria
+    pub serve_rule: ServeRule, // Quem saca depois de cada ponto
+}
+
+impl Default for MatchRules {
+    fn default() -> Self {
+        Self {
+            points_to_win: 11,
+            win_by_two: true,
+            best_of: 1,
+            serve_rule: ServeRule::default(),
+        }
+    }
+}
+
+impl MatchRules {
+    // Lado que venceu o set com esse placar, se algum
+    pub fn set_winner(&self, left: u32, right: u32) -> Option<Scorer> {
+        let margin = if self.win_by_two { 2 } else { 1 };
+        if left >= self.points_to_win && left >= right + margin {
+            Some(Scorer::Left)
+        } else if right >= self.points_to_win && right >= left + margin {
+            Some(Scorer::Right)
+        } else {
+            None
+        }
+    }
+
+    // Sets necessários para vencer a partida
+    pub fn sets_to_win(&self) -> u32 {
+        self.best_of / 2 + 1
+    }
+
+    // Lado que saca depois de um ponto
+    pub fn next_server(&self, scorer: Scorer, server: Scorer) -> Scorer {
+        match self.serve_rule {
+            ServeRule::Conceder => scorer.opponent(),
+            ServeRule::Scorer => scorer,
+            ServeRule::Alternate => server.opponent(),
+        }
+    }
+}
+
+impl Scorer {
+    // O outro lado
+    pub fn opponent(self) -> Self {
+        match self {
+            Scorer::Left => Scorer::Right,
+            Scorer::Right => Scorer::Left,
+        }
+    }
+
+    // Sentido horizontal de quem saca deste lado, em direção ao adversário
+    pub fn serve_direction(self) -> f32 {
+        match self {
+            Scorer::Left => 1.,
+            Scorer::Right => -1.,
+        }
+    }
+}
+
+// Faixa de ângulos do saque em relação à horizontal: evita saques retos demais, que não
+// testam o adversário, e íngremes demais, que ficam batendo nas paredes
+pub const MIN_SERVE_ANGLE: f32 = std::f32::consts::PI / 12.; // 15°
+pub const MAX_SERVE_ANGLE: f32 = std::f32::consts::PI / 4.; // 45°
+
+// Direção (unitária) de um saque com ângulo sorteado, para cima ou para baixo, em direção ao
+// adversário de quem saca
+pub fn random_serve_direction(server: Scorer, rng: &mut impl Rng) -> Vec2 {
+    let angle = rng.random_range(MIN_SERVE_ANGLE..=MAX_SERVE_ANGLE);
+    let vertical = if rng.random_bool(0.5) { 1. } else { -1. };
+    Vec2::new(
+        server.serve_direction() * angle.cos(),
+        vertical * angle.sin(),
+    )
+}
+
+// Recurso com o lado que faz o próximo saque; cada partida começa com o saque da direita
+#[derive(Resource, Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Server(pub Scorer);
+
+impl Default for Server {
+    fn default() -> Self {
+        Self(Scorer::Right)
+    }
+}
+
+// Evento disparado quando um lado vence a partida
+#[derive(Event, Debug, PartialEq, Eq, Copy, Clone)]
+pub struct MatchWon(pub Scorer);
+
+// Sistema que aplica as regras a cada ponto: troca quem saca, fecha os sets e encerra a
+// partida. Roda depois de `update_score`, com o placar já atualizado.
+pub fn apply_match_rules(
+    mut events: EventReader<Scored>,
+    rules: Res<MatchRules>,
+    mut score: ResMut<Score>,
+    mut server: ResMut<Server>,
+    mut match_won: EventWriter<MatchWon>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for Scored(scorer) in events.read() {
+        server.0 = rules.next_server(*scorer, server.0);
+
+        let Some(winner) = rules.set_winner(score.left, score.right) else {
+            continue;
+        };
+        match winner {
+            Scorer::Left => score.left_sets += 1,
+            Scorer::Right => score.right_sets += 1,
+        }
+
+        let sets = match winner {
+            Scorer::Left => score.left_sets,
+            Scorer::Right => score.right_sets,
+        };
+        if sets >= rules.sets_to_win() {
+            // Mantém os pontos do último set para a tela de fim de jogo
+            match_won.send(MatchWon(winner));
+            next_state.set(GameState::GameOver);
+        } else {
+            // Novo set
+            score.left = 0;
+            score.right = 0;
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+
+use crate::InGame;
+
+// Componente para exibir a pontuação do lado esquerdo
+#[derive(Component)]
+pub struct LeftScore;
+
+// Componente para exibir a pontuação do lado direito
+#[derive(Component)]
+pub struct RightScore;
+
+// Recurso para armazenar a pontuação atual do jogo
+#[derive(Resource, Default)]
+pub struct Score {
+    pub left: u32,  // Pontos de quem joga na esquerda (IA ou segundo jogador) no set atual
+    pub right: u32, // Pontos de quem joga na direita (jogador) no set atual
+    pub left_sets: u32, // Sets vencidos pela esquerda
+    pub right_sets: u32, // Sets vencidos pela direita
+}
+
+// Enum para identificar qual lado marcou ponto
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Scorer {
+    Left,  // Lado esquerdo marcou ponto
+    Right, // Lado direito marcou ponto
+}
+
+// Evento disparado quando alguém marca ponto
+#[derive(Event)]
+pub struct Scored(pub Scorer);
+
+// Sistema para atualizar visualmente o placar
+pub fn update_scoreboard(
+    mut left_score: Query<&mut Text, With<LeftScore>>,
+    mut right_score: Query<&mut Text, (With<RightScore>, Without<LeftScore>)>,
+    score: Res<Score>,
+) {
+    if score.is_changed() {
+        // Só atualiza quando a pontuação mudar
+        if let Ok(mut left_score) = left_score.get_single_mut() {
+            left_score.0 = score.left.to_string(); // Atualiza texto do lado esquerdo
+        }
+
+        if let Ok(mut right_score) = right_score.get_single_mut() {
+            right_score.0 = score.right.to_string(); // Atualiza texto do lado direito
+        }
+    }
+}
+
+// Sistema para criar o placar visual
+pub fn spawn_scoreboard(mut commands: Commands) {
+    // Cria texto para pontuação do lado direito
+    commands.spawn((
+        RightScore,
+        Text::new("0"),
+        TextFont {
+            font_size: 72.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(5.0),
+            right: Val::Px(15.0),
+            ..default()
+        },
+        StateScoped(InGame),
+    ));
+
+    // Cria texto para pontuação do lado esquerdo
+    commands.spawn((
+        LeftScore,
+        Text::new("0"),
+        TextFont {
+            font_size: 72.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(5.0),
+            left: Val::Px(15.0),
+            ..default()
+        },
+        StateScoped(InGame),
+    ));
+}
+
+// Sistema para atualizar o recurso de pontuação
+pub fn update_score(mut score: ResMut<Score>, mut events: EventReader<Scored>) {
+    for event in events.read() {
+        match event.0 {
+            Scorer::Left => score.left += 1, // Incrementa pontuação do lado esquerdo
+            Scorer::Right => score.right += 1, // Incrementa pontuação do lado direito
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use bevy::{prelude::*, render::camera::ScalingMode};
+
+use crate::{
+    Ai, Arena, Ball, GameConfig, GameMode, InGame, MatchGeometry, Paddle, Player, PlayerSlot,
+};
+
+// Sistema para configurar a câmera 2D mostrando exatamente a arena lógica
+pub fn spawn_camera(mut commands: Commands, arena: Res<Arena>) {
+    commands.spawn((
+        Camera2d,
+        OrthographicProjection {
+            scaling_mode: ScalingMode::Fixed {
+                width: arena.width(),
+                height: arena.height(),
+            },
+            ..OrthographicProjection::default_2d()
+        },
+    ));
+}
+
+// Sistema para criar a bola no centro da tela
+pub fn spawn_ball(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    config: Res<GameConfig>,
+    geometry: Res<MatchGeometry>,
+) {
+    debug!("Spawning ball..."); // Log de depuração
+
+    let shape = Circle::new(geometry.ball_radius);
+
+    let mesh = meshes.add(shape);
+    let material = materials.add(config.ball_color);
+
+    // Cria a entidade da bola; cada versão adiciona a física via observadores de `Ball`
+    commands.spawn((
+        Ball,
+        Mesh2d(mesh),
+        MeshMaterial2d(material),
+        StateScoped(InGame),
+    ));
+}
+
+// Sistema para criar as raquetes do jogador (direita) e da IA ou do segundo jogador (esquerda)
+pub fn spawn_paddles(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    arena: Res<Arena>,
+    config: Res<GameConfig>,
+    geometry: Res<MatchGeometry>,
+    mode: Res<GameMode>,
+) {
+    debug!("Spawning paddles..."); // Log de depuração
+
+    // Calcula a posição das raquetes
+    let right_paddle_x = arena.half_size().x - config.paddle_padding; // Raquete do jogador à direita
+    let left_paddle_x = -arena.half_size().x + config.paddle_padding; // Raquete da IA ou do segundo jogador à esquerda
+
+    let shape = Rectangle::from_size(geometry.paddle_size);
+
+    let mesh = meshes.add(shape);
+    let player_color = materials.add(Color::srgb(0., 1., 0.)); // Verde para o jogador
+    let left_color = materials.add(Color::srgb(0., 0., 1.)); // Azul para a esquerda
+
+    // Cria a raquete do jogador
+    commands.spawn((
+        Player,
+        Paddle,
+        PlayerSlot(0), // Controles do primeiro jogador
+        Transform::from_xyz(right_paddle_x, 0., 0.),
+        Mesh2d(mesh.clone()),
+        MeshMaterial2d(player_color),
+        StateScoped(InGame),
+    ));
+
+    // Cria a raquete da esquerda
+    let left = commands
+        .spawn((
+            Paddle,
+            Transform::from_xyz(left_paddle_x, 0., 0.),
+            Mesh2d(mesh),
+            MeshMaterial2d(left_color),
+            StateScoped(InGame),
+        ))
+        .id();
+    match *mode {
+        GameMode::SinglePlayer => commands.entity(left).insert(Ai),
+        GameMode::TwoPlayers => commands.entity(left).insert(PlayerSlot(1)), // Controles do segundo jogador
+    };
+}
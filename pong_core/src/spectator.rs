@@ -0,0 +1,221 @@
+use std::{
+    io::{self, ErrorKind, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+};
+
+use bevy::prelude::*;
+
+use crate::{Arena, BallSample, MatchGeometry, Paddle, Player, Score};
+
+pub const SPECTATOR_RATE: f32 = 30.; // Quadros enviados aos espectadores por segundo
+pub const SPECTATOR_FRAME_SIZE: usize = 13 * 4 + 4; // Bytes de cada quadro: 13 `f32` e 4 bytes de placar
+
+// Estado da partida enviado aos espectadores: o suficiente para desenhar a arena, a bola, as
+// raquetes e o placar. Cada quadro ocupa sempre `SPECTATOR_FRAME_SIZE` bytes (veja `encode`)
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SpectatorFrame {
+    pub arena: Vec2,         // Tamanho da arena
+    pub ball: Vec2,          // Posição da bola
+    pub ball_velocity: Vec2, // Velocidade da bola, em unidades por segundo
+    pub ball_radius: f32,    // Raio da bola
+    pub right_paddle: Vec2,  // Posição da raquete da direita
+    pub left_paddle: Vec2,   // Posição da raquete da esquerda
+    pub paddle_size: Vec2,   // Tamanho das raquetes
+    pub left: u32,           // Pontos da esquerda no set atual
+    pub right: u32,          // Pontos da direita no set atual
+    pub left_sets: u32,      // Sets vencidos pela esquerda
+    pub right_sets: u32,     // Sets vencidos pela direita
+}
+
+impl SpectatorFrame {
+    // Codifica o quadro em um formato binário fixo: os vetores e o raio como `f32` little-endian,
+    // na ordem dos campos, seguidos de um byte para cada número do placar (até 255)
+    pub fn encode(&self) -> [u8; SPECTATOR_FRAME_SIZE] {
+        let floats = [
+            self.arena.x,
+            self.arena.y,
+            self.ball.x,
+            self.ball.y,
+            self.ball_velocity.x,
+            self.ball_velocity.y,
+            self.ball_radius,
+            self.right_paddle.x,
+            self.right_paddle.y,
+            self.left_paddle.x,
+            self.left_paddle.y,
+            self.paddle_size.x,
+            self.paddle_size.y,
+        ];
+        let score = [self.left, self.right, self.left_sets, self.right_sets];
+
+        let mut bytes = [0; SPECTATOR_FRAME_SIZE];
+        for (chunk, value) in bytes.chunks_exact_mut(4).zip(floats) {
+            chunk.copy_from_slice(&value.to_le_bytes());
+        }
+        for (byte, value) in bytes[floats.len() * 4..].iter_mut().zip(score) {
+            *byte = value.min(u8::MAX.into()) as u8;
+        }
+        bytes
+    }
+
+    // Lê um quadro codificado por `encode`; `None` se o tamanho não confere
+    pub fn decode(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != SPECTATOR_FRAME_SIZE {
+            return None;
+        }
+        let float = |index: usize| {
+            let start = index * 4;
+            f32::from_le_bytes(bytes[start..start + 4].try_into().unwrap())
+        };
+        let vector = |index| Vec2::new(float(index), float(index + 1));
+        let score = |index: usize| u32::from(bytes[13 * 4 + index]);
+
+        Some(Self {
+            arena: vector(0),
+            ball: vector(2),
+            ball_velocity: vector(4),
+            ball_radius: float(6),
+            right_paddle: vector(7),
+            left_paddle: vector(9),
+            paddle_size: vector(11),
+            left: score(0),
+            right: score(1),
+            left_sets: score(2),
+            right_sets: score(3),
+        })
+    }
+}
+
+// Recurso do servidor de espectadores: aceita conexões TCP e envia o estado da partida a todos
+// os conectados. Clientes que desconectam ou não acompanham o ritmo são descartados
+#[derive(Resource)]
+pub struct SpectatorServer {
+    listener: TcpListener,   // Socket que aceita os espectadores, sem bloqueio
+    clients: Vec<TcpStream>, // Espectadores conectados
+    timer: Timer,            // Intervalo entre os quadros enviados
+}
+
+impl SpectatorServer {
+    // Abre o servidor no endereço indicado
+    pub fn bind(address: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self {
+            listener,
+            clients: Vec::new(),
+            timer: Timer::from_seconds(1. / SPECTATOR_RATE, TimerMode::Repeating),
+        })
+    }
+
+    // Endereço em que o servidor escuta, útil quando a porta é escolhida pelo sistema
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    // Quantos espectadores estão conectados
+    pub fn spectators(&self) -> usize {
+        self.clients.len()
+    }
+
+    // Aceita os espectadores que chegaram desde o último quadro
+    fn accept(&mut self) {
+        while let Ok((stream, address)) = self.listener.accept() {
+            if stream.set_nonblocking(true).is_ok() {
+                info!("Espectador conectado: {address}");
+                self.clients.push(stream);
+            }
+        }
+    }
+
+    // Envia um quadro a todos os espectadores
+    fn broadcast(&mut self, frame: &SpectatorFrame) {
+        let bytes = frame.encode();
+        self.clients
+            .retain_mut(|client| client.write_all(&bytes).is_ok());
+    }
+}
+
+// Sistema que envia o estado da partida aos espectadores no ritmo de `SPECTATOR_RATE`, usando o
+// tempo real para continuar transmitindo durante a pausa
+pub fn broadcast_to_spectators(
+    mut server: ResMut<SpectatorServer>,
+    time: Res<Time<Real>>,
+    sample: Res<BallSample>,
+    paddles: Query<(&Transform, Has<Player>), With<Paddle>>,
+    score: Res<Score>,
+    arena: Res<Arena>,
+    geometry: Res<MatchGeometry>,
+) {
+    server.accept();
+    if !server.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let mut frame = SpectatorFrame {
+        arena: arena.size,
+        ball: sample.position,
+        ball_velocity: sample.velocity,
+        ball_radius: geometry.ball_radius,
+        paddle_size: geometry.paddle_size,
+        left: score.left,
+        right: score.right,
+        left_sets: score.left_sets,
+        right_sets: score.right_sets,
+        ..default()
+    };
+    for (transform, right) in &paddles {
+        let position = transform.translation.truncate();
+        if right {
+            frame.right_paddle = position;
+        } else {
+            frame.left_paddle = position;
+        }
+    }
+    server.broadcast(&frame);
+}
+
+// Recurso do lado do espectador: lê os quadros enviados pelo servidor
+#[derive(Resource)]
+pub struct SpectatorClient {
+    stream: TcpStream, // Conexão com o servidor, sem bloqueio
+    buffer: Vec<u8>,   // Bytes recebidos que ainda não formam um quadro completo
+    connected: bool,   // Se o servidor ainda está conectado
+}
+
+impl SpectatorClient {
+    // Conecta ao servidor de espectadores
+    pub fn connect(address: impl ToSocketAddrs) -> io::Result<Self> {
+        let stream = TcpStream::connect(address)?;
+        stream.set_nonblocking(true)?;
+        Ok(Self {
+            stream,
+            buffer: Vec::new(),
+            connected: true,
+        })
+    }
+
+    pub fn connected(&self) -> bool {
+        self.connected
+    }
+
+    // Lê o que chegou e devolve o quadro mais recente, se algum chegou completo
+    pub fn poll(&mut self) -> Option<SpectatorFrame> {
+        let mut chunk = [0; 4096];
+        while self.connected {
+            match self.stream.read(&mut chunk) {
+                Ok(0) => self.connected = false,
+                Ok(size) => self.buffer.extend_from_slice(&chunk[..size]),
+                Err(error) if error.kind() == ErrorKind::WouldBlock => break,
+                Err(_) => self.connected = false,
+            }
+        }
+
+        // Só o último quadro completo interessa; os anteriores já ficaram para trás
+        let complete = self.buffer.len() / SPECTATOR_FRAME_SIZE * SPECTATOR_FRAME_SIZE;
+        if complete == 0 {
+            return None;
+        }
+        let frames: Vec<u8> = self.buffer.drain(..complete).collect();
+        SpectatorFrame::decode(&frames[complete - SPECTATOR_FRAME_SIZE..])
+    }
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+use crate::GameConfig;
+
+// Como a velocidade da bola cresce a cada toque que acelera. Todas as curvas partem de
+// `ball_speed` no saque e param em `max_ball_speed`
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum SpeedCurve {
+    #[default]
+    Linear, // Soma `speed_increment` a cada toque
+    Multiplicative(f32), // Multiplica a velocidade pelo fator a cada toque, como 1.05
+    Logarithmic(f32), // Como a linear no começo, com aumentos cada vez menores; o valor é em quantos toques o aumento cai pela metade
+    Capped(f32),      // Como a linear, até a velocidade indicada
+}
+
+impl SpeedCurve {
+    // Velocidade depois de mais um toque, sabendo quantos toques já aceleraram a bola
+    pub fn next_speed(&self, speed: f32, increment: f32, hits: u32) -> f32 {
+        match *self {
+            Self::Linear => speed + increment,
+            Self::Multiplicative(factor) => speed * factor,
+            Self::Logarithmic(scale) => speed + increment * scale / (scale + hits as f32),
+            Self::Capped(cap) => (speed + increment).min(cap).max(speed),
+        }
+    }
+}
+
+impl GameConfig {
+    // Velocidade da bola depois de mais um toque que acelera, sem passar de `max_ball_speed`
+    pub fn next_ball_speed(&self, speed: f32, hits: u32) -> f32 {
+        self.speed_curve
+            .next_speed(speed, self.speed_increment, hits)
+            .min(self.max_ball_speed)
+    }
+}
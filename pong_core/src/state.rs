@@ -0,0 +1,254 @@
+use bevy::prelude::*;
+
+use crate::{
+    Action, AiSettings, Difficulty, GameConfig, GameMode, MatchRules, PlayerActions, Score, Scored,
+    Server,
+};
+
+// Estados do jogo. A partida começa no menu; cada ponto volta para `Serving`, que segura a
+// bola até o fim da contagem regressiva ou até alguém sacar.
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum GameState {
+    #[default]
+    MainMenu, // Tela inicial
+    Serving,  // Bola parada no centro durante a contagem do saque
+    Playing,  // Bola em jogo
+    Paused,   // Partida congelada
+    GameOver, // Partida encerrada
+}
+
+// Estado calculado que existe enquanto há uma partida em andamento (sacando, jogando ou pausada).
+// A arena é criada ao entrar nele e removida ao sair, então reiniciar recria tudo do zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InGame;
+
+impl ComputedStates for InGame {
+    type SourceStates = GameState;
+
+    fn compute(state: GameState) -> Option<Self> {
+        match state {
+            GameState::Serving | GameState::Playing | GameState::Paused => Some(InGame),
+            GameState::MainMenu | GameState::GameOver => None,
+        }
+    }
+}
+
+// Condição para os sistemas das raquetes: elas se movem no saque e durante o jogo
+pub fn paddles_can_move(state: Option<Res<State<GameState>>>) -> bool {
+    state.is_some_and(|state| matches!(state.get(), GameState::Serving | GameState::Playing))
+}
+
+// Recurso presente durante uma partida online. A sessão de rede conduz a partida dos dois
+// lados, então as teclas não trocam o estado local: uma pausa só congelaria a tela enquanto a
+// simulação continua
+#[derive(Resource, Debug, Default)]
+pub struct OnlineMatch;
+
+// Sistema que troca de estado conforme as teclas de saque e pausa de qualquer jogador. A pausa
+// lembra o estado de onde veio e volta para ele: pausar durante a contagem volta ao saque, que
+// recomeça a contagem, em vez de pular direto para o jogo com a bola parada
+pub fn handle_state_input(
+    actions: Res<PlayerActions>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut paused_from: Local<Option<GameState>>,
+) {
+    let pressed = |action| actions.0.iter().any(|state| state.just_pressed(action));
+
+    let next = match *state.get() {
+        GameState::MainMenu if pressed(Action::Serve) => GameState::Serving,
+        GameState::Serving if pressed(Action::Serve) => GameState::Playing,
+        current @ (GameState::Serving | GameState::Playing) if pressed(Action::Pause) => {
+            *paused_from = Some(current);
+            GameState::Paused
+        }
+        GameState::Paused if pressed(Action::Pause) => {
+            paused_from.take().unwrap_or(GameState::Playing)
+        }
+        GameState::GameOver if pressed(Action::Serve) => GameState::Serving, // Revanche
+        GameState::GameOver if pressed(Action::Pause) => GameState::MainMenu,
+        _ => return,
+    };
+    next_state.set(next);
+}
+
+// Sistema que volta para o saque depois de cada ponto
+pub fn serve_after_point(
+    mut events: EventReader<Scored>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if events.read().count() > 0 {
+        next_state.set(GameState::Serving);
+    }
+}
+
+// Recurso com a contagem regressiva do saque; ao terminar, a bola é liberada
+#[derive(Resource, Debug)]
+pub struct ServeCountdown(pub Timer);
+
+// Componente para o texto da contagem regressiva
+#[derive(Component)]
+pub struct CountdownText;
+
+// Sistema que reinicia a contagem regressiva ao entrar no saque
+pub fn start_serve_countdown(mut commands: Commands, config: Res<GameConfig>) {
+    commands.insert_resource(ServeCountdown(Timer::from_seconds(
+        config.serve_delay,
+        TimerMode::Once,
+    )));
+}
+
+// Sistema que avança a contagem regressiva, mostra os segundos restantes e saca ao terminar
+pub fn tick_serve_countdown(
+    time: Res<Time>,
+    mut countdown: ResMut<ServeCountdown>,
+    mut text: Query<&mut Text, With<CountdownText>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    countdown.0.tick(time.delta());
+
+    if let Ok(mut text) = text.get_single_mut() {
+        let seconds = countdown.0.remaining_secs().ceil() as u32;
+        text.0 = seconds.to_string();
+    }
+
+    if countdown.0.finished() {
+        next_state.set(GameState::Playing);
+    }
+}
+
+// Sistema que zera o placar e o saque no início de cada partida
+pub fn reset_match(mut score: ResMut<Score>, mut server: ResMut<Server>) {
+    *score = Score::default();
+    *server = Server::default();
+}
+
+// Texto centralizado na tela, removido ao sair do estado indicado
+fn message(text: impl Into<String>, font_size: f32, top: f32, state: GameState) -> impl Bundle {
+    (
+        Text::new(text),
+        TextFont {
+            font_size,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            top: Val::Percent(top),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        StateScoped(state),
+    )
+}
+
+// Componente para o texto com a dificuldade da IA no menu inicial
+#[derive(Component)]
+pub struct DifficultyText;
+
+fn difficulty_label(difficulty: Difficulty) -> String {
+    format!(
+        "IA: < {} >  (para cima ou para baixo muda)",
+        difficulty.name()
+    )
+}
+
+// Sistema para criar a tela inicial; contra a IA mostra também a dificuldade
+pub fn spawn_main_menu(mut commands: Commands, mode: Res<GameMode>, difficulty: Res<Difficulty>) {
+    commands.spawn(message("PONG", 144.0, 30.0, GameState::MainMenu));
+    commands.spawn(message(
+        "Espaço ou A para jogar",
+        36.0,
+        55.0,
+        GameState::MainMenu,
+    ));
+    if *mode == GameMode::SinglePlayer {
+        commands.spawn((
+            DifficultyText,
+            message(
+                difficulty_label(*difficulty),
+                28.0,
+                65.0,
+                GameState::MainMenu,
+            ),
+        ));
+    }
+}
+
+// Sistema do menu inicial que troca a dificuldade da IA com os comandos de subir e descer
+// de qualquer jogador
+pub fn choose_difficulty(
+    actions: Res<PlayerActions>,
+    mut difficulty: ResMut<Difficulty>,
+    mut settings: ResMut<AiSettings>,
+    mut text: Query<&mut Text, With<DifficultyText>>,
+) {
+    let pressed = |action| actions.0.iter().any(|state| state.just_pressed(action));
+    let chosen = if pressed(Action::MoveUp) {
+        difficulty.harder()
+    } else if pressed(Action::MoveDown) {
+        difficulty.easier()
+    } else {
+        return;
+    };
+    if chosen == *difficulty {
+        return;
+    }
+
+    *difficulty = chosen;
+    *settings = chosen.into();
+    if let Ok(mut text) = text.get_single_mut() {
+        text.0 = difficulty_label(chosen);
+    }
+}
+
+// Sistema para criar a contagem regressiva e a dica de saque
+pub fn spawn_serve_hint(mut commands: Commands, config: Res<GameConfig>) {
+    commands.spawn((
+        CountdownText,
+        message(
+            (config.serve_delay.ceil() as u32).to_string(),
+            96.0,
+            30.0,
+            GameState::Serving,
+        ),
+    ));
+    commands.spawn(message(
+        "Espaço ou A para sacar agora",
+        36.0,
+        70.0,
+        GameState::Serving,
+    ));
+}
+
+// Sistema para criar a tela de pausa
+pub fn spawn_pause_screen(mut commands: Commands) {
+    commands.spawn(message("PAUSA", 96.0, 40.0, GameState::Paused));
+}
+
+// Sistema para criar a tela de fim de jogo com o placar final
+pub fn spawn_game_over_screen(mut commands: Commands, score: Res<Score>, rules: Res<MatchRules>) {
+    let winner = if score.left_sets > score.right_sets {
+        "ESQUERDA VENCEU"
+    } else {
+        "DIREITA VENCEU"
+    };
+    // Com mais de um set, mostra os sets vencidos em vez dos pontos do último set
+    let result = if rules.best_of > 1 {
+        format!("{} x {} sets", score.left_sets, score.right_sets)
+    } else {
+        format!("{} x {}", score.left, score.right)
+    };
+
+    commands.spawn(message("FIM DE JOGO", 96.0, 20.0, GameState::GameOver));
+    commands.spawn(message(winner, 48.0, 35.0, GameState::GameOver));
+    commands.spawn(message(result, 72.0, 45.0, GameState::GameOver));
+    commands.spawn(message(
+        "Espaço ou A para jogar de novo, Esc ou Start para o menu",
+        36.0,
+        60.0,
+        GameState::GameOver,
+    ));
+}
@@ -0,0 +1,118 @@
+use bevy::prelude::*;
+use pong_core::{predict_intercept, AiController, AiSettings, AiView, Difficulty};
+use rand::{rngs::StdRng, SeedableRng};
+
+fn view(paddle: Vec2, ball: Vec2, ball_velocity: Vec2) -> AiView {
+    AiView {
+        paddle,
+        ball,
+        ball_velocity,
+        floor: -100.,
+        ceiling: 100.,
+    }
+}
+
+#[test]
+fn intercept_without_bounces_follows_the_line() {
+    let y = predict_intercept(Vec2::ZERO, Vec2::new(-2., 1.), -100., -100., 100.);
+    assert_eq!(y, Some(50.));
+}
+
+#[test]
+fn intercept_reflects_off_the_walls() {
+    // Sem paredes a bola chegaria a y = 150; reflete no teto (100) e volta para 50
+    let once = predict_intercept(Vec2::ZERO, Vec2::new(-2., 3.), -100., -100., 100.);
+    assert_eq!(once, Some(50.));
+
+    // Sem paredes chegaria a y = -450: reflete no chão, no teto e de novo no chão
+    let thrice = predict_intercept(Vec2::ZERO, Vec2::new(2., -9.), 100., -100., 100.);
+    assert_eq!(thrice, Some(-50.));
+}
+
+#[test]
+fn no_intercept_when_ball_moves_away() {
+    let y = predict_intercept(Vec2::ZERO, Vec2::new(1., 1.), -100., -100., 100.);
+    assert_eq!(y, None);
+}
+
+#[test]
+fn steering_respects_the_maximum_speed() {
+    let settings = AiSettings::from(Difficulty::Easy);
+    let mut controller = AiController::default();
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let direction = controller.steer(
+        &settings,
+        &view(
+            Vec2::new(-100., -90.),
+            Vec2::new(0., 90.),
+            Vec2::new(-1., 0.),
+        ),
+        1.,
+        &mut rng,
+    );
+
+    assert_eq!(direction, settings.max_speed);
+}
+
+#[test]
+fn target_only_changes_after_the_reaction_delay() {
+    let settings = AiSettings::from(Difficulty::Hard);
+    let mut controller = AiController::default();
+    let mut rng = StdRng::seed_from_u64(0);
+    let paddle = Vec2::new(-100., 0.);
+
+    controller.steer(
+        &settings,
+        &view(paddle, Vec2::ZERO, Vec2::new(-2., 1.)),
+        settings.reaction_delay,
+        &mut rng,
+    );
+    assert_eq!(controller.target, 50.);
+
+    // A bola muda de rumo, mas a IA ainda não teve tempo de reagir
+    let turned = view(paddle, Vec2::ZERO, Vec2::new(-2., -1.));
+    controller.steer(&settings, &turned, settings.reaction_delay / 2., &mut rng);
+    assert_eq!(controller.target, 50.);
+    controller.steer(&settings, &turned, settings.reaction_delay / 2., &mut rng);
+    assert_eq!(controller.target, -50.);
+}
+
+#[test]
+fn error_is_rolled_once_per_approach() {
+    let settings = AiSettings::from(Difficulty::Normal);
+    let mut controller = AiController::default();
+    let mut rng = StdRng::seed_from_u64(0);
+    let paddle = Vec2::new(-100., 0.);
+    let coming = view(paddle, Vec2::ZERO, Vec2::new(-2., 1.));
+    let leaving = view(paddle, Vec2::ZERO, Vec2::new(2., 1.));
+
+    // Várias decisões durante a mesma aproximação miram sempre no mesmo ponto
+    controller.steer(&settings, &coming, settings.reaction_delay, &mut rng);
+    let first = controller.target;
+    assert!((first - 50.).abs() <= settings.error_margin, "{first}");
+    for _ in 0..20 {
+        controller.steer(&settings, &coming, settings.reaction_delay, &mut rng);
+        assert_eq!(controller.target, first);
+    }
+
+    // A bola volta e vem de novo: um novo erro é sorteado
+    controller.steer(&settings, &leaving, settings.reaction_delay, &mut rng);
+    controller.steer(&settings, &coming, settings.reaction_delay, &mut rng);
+    assert_ne!(controller.target, first);
+    assert!((controller.target - 50.).abs() <= settings.error_margin);
+}
+
+#[test]
+fn harder_levels_react_faster_and_err_less() {
+    let easy = AiSettings::from(Difficulty::Easy);
+    let normal = AiSettings::from(Difficulty::Normal);
+    let hard = AiSettings::from(Difficulty::Hard);
+
+    assert!(easy.reaction_delay > normal.reaction_delay);
+    assert!(normal.reaction_delay > hard.reaction_delay);
+    assert!(easy.error_margin > normal.error_margin);
+    assert!(normal.error_margin > hard.error_margin);
+    assert!(!easy.prediction && hard.prediction);
+    assert_eq!(AiSettings::default(), normal);
+}
@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+use pong_core::{letterbox_viewport, Arena, ARENA_HEIGHT, ARENA_WIDTH};
+
+#[test]
+fn default_arena_is_fixed_logical_size() {
+    assert_eq!(Arena::default().size, Vec2::new(ARENA_WIDTH, ARENA_HEIGHT));
+    assert_eq!(Arena::default().half_size(), Vec2::new(960., 540.));
+}
+
+#[test]
+fn viewport_fills_window_with_same_aspect_ratio() {
+    let viewport = letterbox_viewport(&Arena::default(), UVec2::new(1280, 720));
+
+    assert_eq!(viewport.physical_position, UVec2::ZERO);
+    assert_eq!(viewport.physical_size, UVec2::new(1280, 720));
+}
+
+#[test]
+fn wide_window_gets_bars_on_the_sides() {
+    let viewport = letterbox_viewport(&Arena::default(), UVec2::new(2560, 1080));
+
+    assert_eq!(viewport.physical_size, UVec2::new(1920, 1080));
+    assert_eq!(viewport.physical_position, UVec2::new(320, 0));
+}
+
+#[test]
+fn tall_window_gets_bars_on_top_and_bottom() {
+    let viewport = letterbox_viewport(&Arena::default(), UVec2::new(1920, 1440));
+
+    assert_eq!(viewport.physical_size, UVec2::new(1920, 1080));
+    assert_eq!(viewport.physical_position, UVec2::new(0, 180));
+}
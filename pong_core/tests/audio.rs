@@ -0,0 +1,102 @@
+use bevy::prelude::*;
+use pong_core::{
+    step, BallHit, GameState, HeadlessPlugin, HitKind, MatchWon, PongPlugin, Scored, Scorer,
+    SoundEffects, SoundSettings,
+};
+
+// Cria o jogo sem janela com os tons disponíveis, como se houvesse áudio
+fn app_with_sounds() -> App {
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugin::default())
+        .init_asset::<Pitch>()
+        .insert_resource(SoundSettings { volume: 0.8 })
+        .add_plugins(PongPlugin);
+    step(&mut app, 1);
+    app
+}
+
+// Sons tocados: o tom e as configurações de cada um
+fn playing(app: &mut App) -> Vec<(Handle<Pitch>, PlaybackSettings)> {
+    let world = app.world_mut();
+    world
+        .query::<(&AudioPlayer<Pitch>, &PlaybackSettings)>()
+        .iter(world)
+        .map(|(player, settings)| (player.0.clone(), *settings))
+        .collect()
+}
+
+#[test]
+fn stays_silent_without_audio() {
+    let mut app = App::new();
+    app.add_plugins((HeadlessPlugin::default(), PongPlugin));
+    step(&mut app, 1);
+
+    app.world_mut()
+        .send_event(BallHit::new(HitKind::Wall, Vec2::ZERO));
+    step(&mut app, 1);
+
+    assert!(!app.world().contains_resource::<SoundEffects>());
+    assert!(playing(&mut app).is_empty());
+}
+
+#[test]
+fn hits_play_their_tone_with_pitch_and_volume() {
+    let mut app = app_with_sounds();
+
+    app.world_mut().send_event(BallHit {
+        kind: HitKind::Paddle,
+        position: Vec2::ZERO,
+        pitch: 1.5,
+    });
+    step(&mut app, 1);
+
+    let sounds = app.world().resource::<SoundEffects>();
+    let paddle = sounds.paddle.clone();
+    let played = playing(&mut app);
+    assert_eq!(played.len(), 1);
+    let (tone, settings) = &played[0];
+    assert_eq!(*tone, paddle);
+    assert_eq!(settings.speed, 1.5);
+    assert_eq!(settings.volume.get(), 0.8);
+}
+
+#[test]
+fn winning_point_plays_only_the_win_sound() {
+    let mut app = app_with_sounds();
+
+    app.world_mut().send_event(Scored(Scorer::Left));
+    step(&mut app, 1);
+    let score = app.world().resource::<SoundEffects>().score.clone();
+    assert_eq!(playing(&mut app)[0].0, score);
+
+    let mut app = app_with_sounds();
+    app.world_mut().send_event(Scored(Scorer::Left));
+    app.world_mut().send_event(MatchWon(Scorer::Left));
+    step(&mut app, 1);
+
+    let win = app.world().resource::<SoundEffects>().win.clone();
+    let played = playing(&mut app);
+    assert_eq!(played.len(), 1);
+    assert_eq!(played[0].0, win);
+}
+
+#[test]
+fn serve_plays_the_serve_tone() {
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugin::default())
+        .init_asset::<Pitch>()
+        .insert_state(GameState::Serving)
+        .add_plugins(PongPlugin);
+    step(&mut app, 1);
+    assert!(playing(&mut app).is_empty());
+
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Playing);
+    step(&mut app, 2);
+
+    let serve = app.world().resource::<SoundEffects>().serve.clone();
+    let played = playing(&mut app);
+    assert_eq!(played.len(), 1);
+    assert_eq!(played[0].0, serve);
+}
@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+use pong_core::{paddle_bounce, GameConfig};
+
+// Configuração sem efeito da raquete, para isolar o ângulo pelo ponto de contato
+fn config_without_spin() -> GameConfig {
+    GameConfig {
+        paddle_spin: 0.,
+        ..default()
+    }
+}
+
+#[test]
+fn centre_hit_returns_straight() {
+    let config = config_without_spin();
+
+    let velocity = paddle_bounce(Vec2::new(300., 0.), -1., 0., 25., 0., &config);
+
+    assert!(velocity.distance(Vec2::new(-300., 0.)) < 1e-3, "{velocity}");
+}
+
+#[test]
+fn edge_hits_leave_at_the_maximum_angle() {
+    let config = config_without_spin();
+
+    for (offset, sign) in [(25., 1.), (-25., -1.), (40., 1.)] {
+        let velocity = paddle_bounce(Vec2::new(-300., 50.), 1., offset, 25., 0., &config);
+
+        assert!(velocity.x > 0.);
+        let angle = velocity.y.atan2(velocity.x);
+        assert!(
+            (angle - sign * config.max_bounce_angle).abs() < 1e-4,
+            "{angle}"
+        );
+        assert!((velocity.length() - Vec2::new(-300., 50.).length()).abs() < 1e-3);
+    }
+}
+
+#[test]
+fn moving_paddle_adds_spin_within_the_maximum_angle() {
+    let config = GameConfig::default();
+
+    let still = paddle_bounce(Vec2::new(300., 0.), -1., 0., 25., 0., &config);
+    let moving = paddle_bounce(Vec2::new(300., 0.), -1., 0., 25., 600., &config);
+    assert_eq!(still.y, 0.);
+    assert!(moving.y > 0., "{moving}");
+
+    let fast = paddle_bounce(Vec2::new(300., 0.), -1., 25., 25., 1e6, &config);
+    let angle = fast.y.atan2(-fast.x);
+    assert!(angle <= config.max_bounce_angle + 1e-4, "{angle}");
+}
@@ -0,0 +1,153 @@
+use bevy::{
+    prelude::*,
+    window::{Monitor, MonitorSelection, PrimaryWindow, WindowMode},
+};
+use pong_core::{
+    apply_display_settings, choose_monitor, run_app, CliOptions, Difficulty, DisplaySettings,
+    GameMode, GameState, HeadlessPlugin, PongPlugin, Replay, WindowChoice,
+};
+
+fn parse(args: &str) -> Result<CliOptions, String> {
+    CliOptions::parse(args.split_whitespace().map(String::from))
+}
+
+#[test]
+fn no_arguments_keep_the_defaults() {
+    let options = parse("").unwrap();
+    assert_eq!(options, CliOptions::default());
+    assert!(!options.headless());
+    assert_eq!(
+        options.display(WindowChoice::Fullscreen).mode,
+        WindowChoice::Fullscreen
+    );
+}
+
+#[test]
+fn parses_window_and_game_options() {
+    let options = parse(
+        "--borderless --monitor 2 --resolution 1280x720 --mode single --difficulty hard --seed 42 --config teste.ron",
+    )
+    .unwrap();
+
+    let display = options.display(WindowChoice::Windowed);
+    assert_eq!(display.mode, WindowChoice::Borderless);
+    assert_eq!(display.monitor, Some(2));
+    assert_eq!(display.resolution, Some(UVec2::new(1280, 720)));
+    assert_eq!(options.mode, Some(GameMode::SinglePlayer));
+    assert_eq!(options.difficulty, Some(Difficulty::Hard));
+    assert_eq!(options.seed, Some(42));
+    assert_eq!(options.config, Some("teste.ron".into()));
+}
+
+#[test]
+fn sim_steps_imply_headless() {
+    let options = parse("--sim 500").unwrap();
+    assert!(options.headless());
+    assert_eq!(options.sim_steps, Some(500));
+    assert!(parse("--headless").unwrap().headless());
+}
+
+#[test]
+fn invalid_arguments_explain_the_problem() {
+    let error = parse("--fast").unwrap_err();
+    assert!(error.contains("--fast"), "{error}");
+
+    let error = parse("--seed").unwrap_err();
+    assert!(error.contains("--seed"), "{error}");
+
+    let error = parse("--monitor dois").unwrap_err();
+    assert!(error.contains("dois"), "{error}");
+
+    assert!(parse("--resolution 1280").is_err());
+    assert!(parse("--resolution 0x720").is_err());
+    assert!(parse("--difficulty impossible").is_err());
+    assert!(parse("--player 2").is_err());
+    assert!(parse("--record a.ron --replay b.ron").is_err());
+}
+
+#[test]
+fn missing_monitor_falls_back_to_the_primary() {
+    assert_eq!(choose_monitor(Some(1), 2), MonitorSelection::Index(1));
+    assert_eq!(choose_monitor(Some(1), 1), MonitorSelection::Primary);
+    assert_eq!(choose_monitor(None, 3), MonitorSelection::Primary);
+}
+
+// App mínimo com uma janela principal e `count` monitores conectados
+fn display_app(settings: DisplaySettings, count: usize) -> App {
+    let mut app = App::new();
+    app.insert_resource(settings)
+        .add_systems(Update, apply_display_settings);
+    app.world_mut().spawn((Window::default(), PrimaryWindow));
+    for _ in 0..count {
+        app.world_mut().spawn(Monitor {
+            name: None,
+            physical_height: 1080,
+            physical_width: 1920,
+            physical_position: IVec2::ZERO,
+            refresh_rate_millihertz: None,
+            scale_factor: 1.,
+            video_modes: Vec::new(),
+        });
+    }
+    app
+}
+
+fn window_mode(app: &mut App) -> WindowMode {
+    let world = app.world_mut();
+    world
+        .query_filtered::<&Window, With<PrimaryWindow>>()
+        .single(world)
+        .mode
+}
+
+#[test]
+fn fullscreen_goes_to_the_requested_monitor_or_the_primary() {
+    let settings = DisplaySettings {
+        mode: WindowChoice::Fullscreen,
+        monitor: Some(1),
+        resolution: None,
+    };
+
+    let mut app = display_app(settings.clone(), 2);
+    app.update();
+    assert_eq!(
+        window_mode(&mut app),
+        WindowMode::Fullscreen(MonitorSelection::Index(1))
+    );
+
+    // Com um só monitor a tela cheia vai para o principal em vez de falhar
+    let mut app = display_app(settings, 1);
+    app.update();
+    assert_eq!(
+        window_mode(&mut app),
+        WindowMode::Fullscreen(MonitorSelection::Primary)
+    );
+}
+
+#[test]
+fn waits_for_the_monitors_before_applying() {
+    let settings = DisplaySettings {
+        mode: WindowChoice::Borderless,
+        ..default()
+    };
+    let mut app = display_app(settings, 0);
+    app.update();
+    assert_eq!(window_mode(&mut app), WindowMode::Windowed);
+}
+
+#[test]
+fn headless_run_simulates_and_saves_the_recording() {
+    let path = std::env::temp_dir().join(format!("pong_cli_record_{}.ron", std::process::id()));
+    let options = parse(&format!("--sim 20 --record {}", path.display())).unwrap();
+
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugin::default())
+        .insert_state(GameState::Serving)
+        .add_plugins(PongPlugin);
+    options.apply_extras(&mut app);
+    run_app(app, &options);
+
+    let replay = Replay::load(&path).unwrap();
+    assert!(replay.frame_count() >= 20, "{}", replay.frame_count());
+    std::fs::remove_file(&path).unwrap();
+}
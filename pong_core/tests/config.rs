@@ -0,0 +1,220 @@
+use std::{
+    fs,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+use bevy::prelude::*;
+use pong_core::{
+    step, ConfigFile, GameConfig, HeadlessPlugin, PongPlugin, ReplayRecorder, CONFIG_POLL_INTERVAL,
+    MAX_BALL_ANGLE, MAX_BALL_SPEED, MIN_HORIZONTAL_SPEED, STALL_TIME,
+};
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("pong_config_{name}_{}.ron", std::process::id()))
+}
+
+// Grava a configuração e avança a data de modificação, para que a mudança seja notada mesmo
+// em sistemas de arquivos com pouca precisão de tempo
+fn save_later(config: &GameConfig, path: &PathBuf, seconds: u64) {
+    config.save(path).unwrap();
+    let file = fs::File::options().write(true).open(path).unwrap();
+    file.set_modified(SystemTime::now() + Duration::from_secs(seconds))
+        .unwrap();
+}
+
+// Jogo sem janela acompanhando o arquivo de configuração indicado
+fn app_watching(path: &PathBuf, configure: impl FnOnce(&mut App)) -> App {
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugin::default())
+        .insert_resource(GameConfig::load(path).unwrap())
+        .insert_resource(ConfigFile::new(path));
+    configure(&mut app);
+    app.add_plugins(PongPlugin);
+    step(&mut app, 1);
+    app
+}
+
+// Passos suficientes para a próxima verificação do arquivo
+fn wait_for_poll(app: &mut App) {
+    step(app, (CONFIG_POLL_INTERVAL * 64.) as u32 + 1);
+}
+
+#[test]
+fn config_round_trips_through_a_file() {
+    let path = temp_path("round_trip");
+    let config = GameConfig {
+        ball_speed: 123.,
+        ball_color: Color::srgb(0., 1., 0.),
+        ..default()
+    };
+
+    config.save(&path).unwrap();
+    let loaded = GameConfig::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded, config);
+}
+
+#[test]
+fn default_config_is_valid() {
+    assert_eq!(GameConfig::default().validate(), Ok(()));
+}
+
+#[test]
+fn validation_lists_every_problem() {
+    let config = GameConfig {
+        ball_radius: 0.,
+        paddle_speed: -1.,
+        speed_increment: f32::NAN,
+        max_bounce_angle: 2.,
+        min_horizontal_speed: -1.,
+        max_ball_angle: 0.5, // Menor que `max_bounce_angle`
+        stall_time: 0.,
+        ..default()
+    };
+
+    let problems = config.validate().unwrap_err();
+
+    for field in [
+        "ball_radius",
+        "paddle_speed",
+        "speed_increment",
+        "max_bounce_angle",
+        "min_horizontal_speed",
+        "max_ball_angle",
+        "stall_time",
+    ] {
+        assert!(problems.contains(field), "faltou `{field}` em: {problems}");
+    }
+}
+
+#[test]
+fn geometry_must_fit_inside_the_arena() {
+    let walls = GameConfig {
+        wall_thickness: 520.,
+        ..default()
+    };
+    let problems = walls.validate().unwrap_err();
+    assert!(problems.contains("paddle_height"), "{problems}");
+
+    let ball = GameConfig {
+        wall_thickness: 530.,
+        ball_radius: 20.,
+        paddle_height: 1.,
+        ..default()
+    };
+    let problems = ball.validate().unwrap_err();
+    assert!(problems.contains("ball_radius"), "{problems}");
+}
+
+#[test]
+fn ball_speed_must_stay_below_the_max() {
+    let config = GameConfig {
+        ball_speed: MAX_BALL_SPEED,
+        ..default()
+    };
+    let problems = config.validate().unwrap_err();
+    assert!(problems.contains("max_ball_speed"), "{problems}");
+}
+
+#[test]
+fn files_without_the_rally_fields_still_load() {
+    let text =
+        ron::ser::to_string_pretty(&GameConfig::default(), ron::ser::PrettyConfig::default())
+            .unwrap();
+    let old_text: String = text
+        .lines()
+        .filter(|line| {
+            !["min_horizontal_speed", "max_ball_angle", "stall_time"]
+                .iter()
+                .any(|field| line.contains(field))
+        })
+        .collect();
+
+    let loaded: GameConfig = ron::from_str(&old_text).unwrap();
+    assert_eq!(loaded.min_horizontal_speed, MIN_HORIZONTAL_SPEED);
+    assert_eq!(loaded.max_ball_angle, MAX_BALL_ANGLE);
+    assert_eq!(loaded.stall_time, STALL_TIME);
+}
+
+#[test]
+fn invalid_file_is_reported_on_load() {
+    let path = temp_path("invalid");
+    let config = GameConfig {
+        ball_speed: 10_000.,
+        ..default()
+    };
+    config.save(&path).unwrap();
+    let error = GameConfig::load(&path).unwrap_err();
+    fs::write(&path, "(ball_radius: 5.)").unwrap();
+    let missing = GameConfig::load(&path).unwrap_err();
+    fs::remove_file(&path).unwrap();
+
+    assert!(error.to_string().contains("max_ball_speed"));
+    assert!(missing.to_string().contains("ball_color"));
+}
+
+#[test]
+fn missing_file_is_created_with_the_defaults() {
+    let path = temp_path("new");
+    let _ = fs::remove_file(&path);
+    let defaults = GameConfig {
+        paddle_height: 80.,
+        ..default()
+    };
+
+    let config = GameConfig::load_or_create(&path, defaults.clone());
+    let saved = GameConfig::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(config, defaults);
+    assert_eq!(saved, defaults);
+}
+
+#[test]
+fn saved_changes_are_reloaded_while_running() {
+    let path = temp_path("reload");
+    GameConfig::default().save(&path).unwrap();
+    let mut app = app_watching(&path, |_| {});
+
+    let faster = GameConfig {
+        ball_speed: 90.,
+        ..default()
+    };
+    save_later(&faster, &path, 10);
+    wait_for_poll(&mut app);
+    assert_eq!(app.world().resource::<GameConfig>().ball_speed, 90.);
+
+    // Um arquivo inválido é ignorado e a configuração anterior continua valendo
+    let broken = GameConfig {
+        ball_radius: -1.,
+        ..faster
+    };
+    save_later(&broken, &path, 20);
+    wait_for_poll(&mut app);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(app.world().resource::<GameConfig>().ball_speed, 90.);
+    assert_eq!(app.world().resource::<GameConfig>().ball_radius, 5.);
+}
+
+#[test]
+fn config_stays_fixed_while_recording() {
+    let path = temp_path("recording");
+    let recording = temp_path("recording_replay");
+    GameConfig::default().save(&path).unwrap();
+    let mut app = app_watching(&path, |app| {
+        app.insert_resource(ReplayRecorder::new(&recording));
+    });
+
+    let faster = GameConfig {
+        ball_speed: 90.,
+        ..default()
+    };
+    save_later(&faster, &path, 10);
+    wait_for_poll(&mut app);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(app.world().resource::<GameConfig>(), &GameConfig::default());
+}
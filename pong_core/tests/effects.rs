@@ -0,0 +1,144 @@
+use bevy::prelude::*;
+use pong_core::{
+    step, trail_lifetime, BallHit, BallSample, EffectSettings, GameState, HeadlessPlugin, HitKind,
+    Paddle, PaddleFlash, Particle, PongPlugin, Scored, Scorer, TrailDot, MAX_TRAIL_TIME,
+    PARTICLES_PER_HIT, SHAKE_TIME, TRAIL_SPEED, TRAIL_TIME,
+};
+
+// Cria o jogo sem janela já jogando, com os efeitos indicados
+fn app_with_effects(settings: EffectSettings) -> App {
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugin::default())
+        .insert_state(GameState::Playing)
+        .insert_resource(settings)
+        .add_plugins(PongPlugin);
+    step(&mut app, 1);
+    app
+}
+
+fn count<T: Component>(app: &mut App) -> usize {
+    let world = app.world_mut();
+    world.query_filtered::<(), With<T>>().iter(world).count()
+}
+
+fn camera_offset(app: &mut App) -> Vec2 {
+    let world = app.world_mut();
+    let mut cameras = world.query_filtered::<&Transform, With<Camera2d>>();
+    cameras.single(world).translation.truncate()
+}
+
+#[test]
+fn trail_gets_longer_with_speed() {
+    assert_eq!(trail_lifetime(0.), 0.);
+    assert_eq!(trail_lifetime(TRAIL_SPEED), TRAIL_TIME);
+    assert!(trail_lifetime(2. * TRAIL_SPEED) > trail_lifetime(TRAIL_SPEED));
+    assert_eq!(trail_lifetime(100. * TRAIL_SPEED), MAX_TRAIL_TIME);
+}
+
+#[test]
+fn moving_ball_leaves_a_fading_trail() {
+    let mut app = app_with_effects(EffectSettings::default());
+    assert_eq!(count::<TrailDot>(&mut app), 0); // Bola parada
+
+    app.world_mut().resource_mut::<BallSample>().velocity = Vec2::new(TRAIL_SPEED, 0.);
+    step(&mut app, 3);
+    assert_eq!(count::<TrailDot>(&mut app), 3);
+
+    app.world_mut().resource_mut::<BallSample>().velocity = Vec2::ZERO;
+    step(&mut app, 10);
+    assert_eq!(count::<TrailDot>(&mut app), 0);
+}
+
+#[test]
+fn trail_and_hits_reuse_the_shared_assets() {
+    let mut app = app_with_effects(EffectSettings::default());
+    app.world_mut()
+        .spawn((Paddle, Transform::from_xyz(100., 0., 0.)));
+    let meshes = app.world().resource::<Assets<Mesh>>().len();
+    let materials = app.world().resource::<Assets<ColorMaterial>>().len();
+
+    app.world_mut().resource_mut::<BallSample>().velocity = Vec2::new(TRAIL_SPEED, 0.);
+    app.world_mut()
+        .send_event(BallHit::new(HitKind::Paddle, Vec2::new(90., 10.)));
+    step(&mut app, 20);
+
+    assert!(count::<TrailDot>(&mut app) > 0);
+    assert_eq!(app.world().resource::<Assets<Mesh>>().len(), meshes);
+    assert_eq!(
+        app.world().resource::<Assets<ColorMaterial>>().len(),
+        materials
+    );
+}
+
+#[test]
+fn paddle_hit_flashes_the_nearest_paddle_and_bursts_particles() {
+    let mut app = app_with_effects(EffectSettings::default());
+    let right = app
+        .world_mut()
+        .spawn((Paddle, Transform::from_xyz(100., 0., 0.)))
+        .id();
+    app.world_mut()
+        .spawn((Paddle, Transform::from_xyz(-100., 0., 0.)));
+
+    app.world_mut()
+        .send_event(BallHit::new(HitKind::Paddle, Vec2::new(90., 10.)));
+    step(&mut app, 1);
+
+    assert_eq!(count::<Particle>(&mut app), PARTICLES_PER_HIT);
+    let world = app.world_mut();
+    let flashes: Vec<Entity> = world
+        .query_filtered::<&Parent, With<PaddleFlash>>()
+        .iter(world)
+        .map(|parent| parent.get())
+        .collect();
+    assert_eq!(flashes, [right]);
+
+    step(&mut app, 30);
+    assert_eq!(count::<Particle>(&mut app), 0);
+    assert_eq!(count::<PaddleFlash>(&mut app), 0);
+}
+
+#[test]
+fn wall_hit_does_not_flash_paddles() {
+    let mut app = app_with_effects(EffectSettings::default());
+    app.world_mut().spawn((Paddle, Transform::default()));
+
+    app.world_mut()
+        .send_event(BallHit::new(HitKind::Wall, Vec2::ZERO));
+    step(&mut app, 1);
+
+    assert_eq!(count::<PaddleFlash>(&mut app), 0);
+    assert_eq!(count::<Particle>(&mut app), PARTICLES_PER_HIT);
+}
+
+#[test]
+fn disabled_effects_spawn_nothing() {
+    let mut app = app_with_effects(EffectSettings::none());
+    app.world_mut().spawn((Paddle, Transform::default()));
+
+    app.world_mut().resource_mut::<BallSample>().velocity = Vec2::new(TRAIL_SPEED, 0.);
+    app.world_mut()
+        .send_event(BallHit::new(HitKind::Paddle, Vec2::ZERO));
+    app.world_mut().send_event(Scored(Scorer::Left));
+    step(&mut app, 2);
+
+    assert_eq!(count::<TrailDot>(&mut app), 0);
+    assert_eq!(count::<Particle>(&mut app), 0);
+    assert_eq!(count::<PaddleFlash>(&mut app), 0);
+    assert_eq!(camera_offset(&mut app), Vec2::ZERO);
+}
+
+#[test]
+fn scoring_shakes_the_screen_then_recentres_the_camera() {
+    let mut app = app_with_effects(EffectSettings {
+        screen_shake: true,
+        ..default()
+    });
+
+    app.world_mut().send_event(Scored(Scorer::Left));
+    step(&mut app, 2);
+    assert_ne!(camera_offset(&mut app), Vec2::ZERO);
+
+    step(&mut app, (SHAKE_TIME * 64.) as u32 + 2);
+    assert_eq!(camera_offset(&mut app), Vec2::ZERO);
+}
@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+use pong_core::{run_simulation, step, Arena, Ball, GameState, HeadlessPlugin, PongPlugin};
+
+// Contador de passos fixos executados
+#[derive(Resource, Default)]
+struct FixedSteps(u32);
+
+fn count_fixed_steps(mut steps: ResMut<FixedSteps>) {
+    steps.0 += 1;
+}
+
+fn headless_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugin::default())
+        .insert_state(GameState::Serving) // Pula o menu para criar a arena
+        .add_plugins(PongPlugin)
+        .init_resource::<FixedSteps>()
+        .add_systems(FixedUpdate, count_fixed_steps);
+    app
+}
+
+#[test]
+fn spawns_ball_and_camera_without_window() {
+    let mut app = headless_app();
+    step(&mut app, 1);
+
+    let world = app.world_mut();
+    assert_eq!(world.query::<&Ball>().iter(world).count(), 1);
+    assert_eq!(world.query::<&Camera2d>().iter(world).count(), 1);
+}
+
+#[test]
+fn arena_keeps_inserted_size_without_window() {
+    let mut app = headless_app();
+    app.insert_resource(Arena::new(800., 600.));
+    step(&mut app, 1);
+
+    assert_eq!(app.world().resource::<Arena>().size, Vec2::new(800., 600.));
+}
+
+#[test]
+fn each_update_runs_one_fixed_step() {
+    let mut app = headless_app();
+    // O primeiro passo apenas inicializa o relógio
+    step(&mut app, 1);
+
+    step(&mut app, 10);
+
+    assert_eq!(app.world().resource::<FixedSteps>().0, 10);
+}
+
+#[test]
+fn run_simulation_stops_at_the_step_limit() {
+    let mut app = headless_app();
+    assert_eq!(run_simulation(&mut app, Some(100)), 100);
+    assert_ne!(
+        *app.world().resource::<State<GameState>>().get(),
+        GameState::GameOver
+    );
+}
@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+use pong_core::{step, Action, HeadlessPlugin, InputMap, PlayerActions, PongPlugin};
+
+fn headless_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((HeadlessPlugin::default(), PongPlugin));
+    step(&mut app, 1);
+    app
+}
+
+#[test]
+fn keyboard_drives_each_player_actions() {
+    let mut app = headless_app();
+    let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keyboard.press(KeyCode::ArrowDown);
+    keyboard.press(KeyCode::KeyW);
+    keyboard.press(KeyCode::Space);
+
+    step(&mut app, 1);
+
+    let actions = app.world().resource::<PlayerActions>();
+    assert_eq!(actions.get(0).movement, -1.);
+    assert_eq!(actions.get(1).movement, 1.);
+    assert!(actions.get(0).just_pressed(Action::Serve));
+    assert!(!actions.get(0).just_pressed(Action::Pause));
+    assert_eq!(actions.get(5).movement, 0.); // Jogador sem controles
+}
+
+#[test]
+fn gamepad_stick_gives_proportional_movement() {
+    let mut app = headless_app();
+    let mut gamepad = Gamepad::default();
+    // Metade do caminho entre a zona morta padrão (0,2) e o fim do eixo
+    gamepad.analog_mut().set(GamepadAxis::LeftStickY, 0.6);
+    app.world_mut().spawn(gamepad);
+
+    step(&mut app, 1);
+
+    let actions = app.world().resource::<PlayerActions>();
+    assert!((actions.get(0).movement - 0.5).abs() < 1e-5);
+    assert_eq!(actions.get(1).movement, 0.); // O segundo controle não está conectado
+}
+
+#[test]
+fn stick_inside_dead_zone_is_ignored() {
+    let mut app = headless_app();
+    let mut gamepad = Gamepad::default();
+    gamepad.analog_mut().set(GamepadAxis::LeftStickY, -0.1);
+    gamepad.digital_mut().press(GamepadButton::Start);
+    app.world_mut().spawn(gamepad);
+
+    step(&mut app, 1);
+
+    let actions = app.world().resource::<PlayerActions>();
+    assert_eq!(actions.get(0).movement, 0.);
+    assert!(actions.get(0).just_pressed(Action::Pause));
+}
+
+#[test]
+fn input_map_round_trips_through_the_config_file() {
+    let path = std::env::temp_dir().join(format!("pong_input_{}.ron", std::process::id()));
+    let mut map = InputMap::default();
+    map.players[1].gamepad = None;
+    map.players[0].dead_zone = 0.35;
+
+    map.save(&path).unwrap();
+    let loaded = InputMap::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded, map);
+}
+
+#[test]
+fn missing_config_file_is_created_with_the_defaults() {
+    let path = std::env::temp_dir().join(format!("pong_input_new_{}.ron", std::process::id()));
+
+    let map = InputMap::load_or_create(&path);
+    let saved = InputMap::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(map, InputMap::default());
+    assert_eq!(saved, map);
+}
@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+use pong_core::{ball_checksum, Action, ActionState, Difficulty, GameMode, Replay, ReplayPlayer};
+
+fn moving(movement: f32) -> Vec<ActionState> {
+    let mut state = ActionState::default();
+    state.movement = movement;
+    vec![state]
+}
+
+#[test]
+fn checksum_changes_with_any_ball_difference() {
+    let position = Vec2::new(10., -3.5);
+    let velocity = Vec2::new(-1., 1.);
+    let checksum = ball_checksum(position, velocity);
+
+    assert_eq!(checksum, ball_checksum(position, velocity));
+    assert_ne!(checksum, ball_checksum(position + Vec2::X * 1e-3, velocity));
+    assert_ne!(checksum, ball_checksum(position, -velocity));
+    assert_ne!(checksum, ball_checksum(velocity, position));
+}
+
+#[test]
+fn repeated_movement_and_frames_are_stored_once() {
+    let mut replay = Replay::default();
+
+    for _ in 0..10 {
+        replay.push_tick(&moving(1.));
+    }
+    replay.push_tick(&moving(0.));
+    for ticks in [1, 1, 1, 2, 0] {
+        replay.push_frame(ticks);
+    }
+
+    assert_eq!(replay.movement, vec![(10, vec![1.]), (1, vec![0.])]);
+    assert_eq!(replay.frames, vec![(3, 1), (1, 2), (1, 0)]);
+    assert_eq!(replay.frame_count(), 5);
+}
+
+#[test]
+fn only_pressed_actions_are_stored_for_each_frame() {
+    let mut replay = Replay::default();
+    let mut serving = ActionState::default();
+    serving.press(Action::Serve);
+
+    replay.push_presses(0, &moving(1.));
+    replay.push_presses(1, &[ActionState::default(), serving]);
+
+    assert_eq!(replay.presses, vec![(1, 1, Action::Serve)]);
+}
+
+#[test]
+fn replay_round_trips_through_a_file() {
+    let path = std::env::temp_dir().join(format!("pong_replay_{}.ron", std::process::id()));
+    let mut pausing = ActionState::default();
+    pausing.press(Action::Pause);
+    let mut replay = Replay {
+        seed: 42,
+        mode: GameMode::TwoPlayers,
+        difficulty: Difficulty::Hard,
+        ai: Difficulty::Hard.into(),
+        ..default()
+    };
+    replay.push_frame(0);
+    replay.push_frame(1);
+    replay.push_presses(1, &[pausing]);
+    replay.push_tick(&moving(-0.5));
+    replay.checksums = vec![1];
+
+    replay.save(&path).unwrap();
+    let loaded = Replay::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded, replay);
+}
+
+#[test]
+fn empty_replay_is_finished_from_the_start() {
+    let player = ReplayPlayer::new(Replay::default());
+
+    assert!(player.finished());
+    assert_eq!(player.diverged(), None);
+}
@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+use pong_core::{random_serve_direction, step, GameRng, HeadlessPlugin, PongPlugin, Scorer};
+use rand::Rng;
+
+#[test]
+fn same_seed_repeats_the_same_sequence() {
+    let mut first = GameRng::new(42);
+    let mut second = GameRng::new(42);
+
+    for _ in 0..10 {
+        assert_eq!(
+            random_serve_direction(Scorer::Left, &mut first),
+            random_serve_direction(Scorer::Left, &mut second)
+        );
+    }
+    assert_eq!(first.random::<u64>(), second.random::<u64>());
+}
+
+#[test]
+fn different_seeds_diverge() {
+    let mut first = GameRng::new(1);
+    let mut second = GameRng::new(2);
+
+    assert_ne!(first.random::<u64>(), second.random::<u64>());
+}
+
+#[test]
+fn plugin_keeps_the_inserted_seed() {
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugin::default())
+        .insert_resource(GameRng::new(7))
+        .add_plugins(PongPlugin);
+    step(&mut app, 1);
+
+    assert_eq!(app.world().resource::<GameRng>().seed(), 7);
+}
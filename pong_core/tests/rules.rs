@@ -0,0 +1,126 @@
+use bevy::prelude::*;
+use pong_core::{
+    step, GameState, HeadlessPlugin, MatchRules, MatchWon, PongPlugin, Score, Scored, Scorer,
+    ServeRule, Server,
+};
+
+// Cria o jogo já em andamento com as regras indicadas
+fn headless_app(rules: MatchRules) -> App {
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugin::default())
+        .insert_state(GameState::Playing)
+        .insert_resource(rules)
+        .add_plugins(PongPlugin);
+    step(&mut app, 1);
+    app
+}
+
+// Marca um ponto e volta a jogar, como se o saque tivesse acontecido
+fn score_point(app: &mut App, scorer: Scorer) {
+    app.world_mut().send_event(Scored(scorer));
+    step(app, 2); // O segundo passo aplica a troca de estado
+    if *app.world().resource::<State<GameState>>().get() == GameState::Serving {
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Playing);
+        step(app, 1);
+    }
+}
+
+#[test]
+fn set_needs_the_points_and_a_two_point_lead() {
+    let rules = MatchRules::default();
+
+    assert_eq!(rules.set_winner(11, 9), Some(Scorer::Left));
+    assert_eq!(rules.set_winner(10, 11), None); // Vantagem de só um ponto
+    assert_eq!(rules.set_winner(12, 14), Some(Scorer::Right));
+    assert_eq!(rules.set_winner(9, 7), None);
+
+    let sudden_death = MatchRules {
+        win_by_two: false,
+        ..default()
+    };
+    assert_eq!(sudden_death.set_winner(11, 10), Some(Scorer::Left));
+}
+
+#[test]
+fn serve_rule_picks_the_next_server() {
+    let rules = |serve_rule| MatchRules {
+        serve_rule,
+        ..default()
+    };
+
+    let (scorer, server) = (Scorer::Left, Scorer::Left);
+    assert_eq!(
+        rules(ServeRule::Conceder).next_server(scorer, server),
+        Scorer::Right
+    );
+    assert_eq!(
+        rules(ServeRule::Scorer).next_server(scorer, server),
+        Scorer::Left
+    );
+    assert_eq!(
+        rules(ServeRule::Alternate).next_server(scorer, server),
+        Scorer::Right
+    );
+    assert_eq!(
+        rules(ServeRule::Alternate).next_server(scorer, Scorer::Right),
+        Scorer::Left
+    );
+}
+
+#[test]
+fn winning_the_match_ends_the_game() {
+    let mut app = headless_app(MatchRules {
+        points_to_win: 3,
+        ..default()
+    });
+
+    for _ in 0..3 {
+        score_point(&mut app, Scorer::Right);
+    }
+
+    let state = app.world().resource::<State<GameState>>();
+    assert_eq!(*state.get(), GameState::GameOver);
+    let events = app.world().resource::<Events<MatchWon>>();
+    let won: Vec<_> = events.get_cursor().read(events).copied().collect();
+    assert_eq!(won, vec![MatchWon(Scorer::Right)]);
+    let score = app.world().resource::<Score>();
+    assert_eq!((score.left, score.right, score.right_sets), (0, 3, 1));
+}
+
+#[test]
+fn sets_restart_the_points_until_the_match_is_decided() {
+    let mut app = headless_app(MatchRules {
+        points_to_win: 2,
+        win_by_two: false,
+        best_of: 3,
+        ..default()
+    });
+
+    score_point(&mut app, Scorer::Left);
+    score_point(&mut app, Scorer::Left);
+
+    let score = app.world().resource::<Score>();
+    assert_eq!((score.left, score.right), (0, 0));
+    assert_eq!((score.left_sets, score.right_sets), (1, 0));
+    let state = app.world().resource::<State<GameState>>();
+    assert_eq!(*state.get(), GameState::Playing);
+
+    score_point(&mut app, Scorer::Left);
+    score_point(&mut app, Scorer::Left);
+
+    let state = app.world().resource::<State<GameState>>();
+    assert_eq!(*state.get(), GameState::GameOver);
+    assert_eq!(app.world().resource::<Score>().left_sets, 2);
+}
+
+#[test]
+fn conceder_serves_after_each_point_by_default() {
+    let mut app = headless_app(MatchRules::default());
+    assert_eq!(*app.world().resource::<Server>(), Server(Scorer::Right));
+
+    score_point(&mut app, Scorer::Right);
+
+    assert_eq!(*app.world().resource::<Server>(), Server(Scorer::Left));
+}
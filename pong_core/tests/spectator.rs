@@ -0,0 +1,114 @@
+use std::{thread, time::Duration};
+
+use bevy::prelude::*;
+use pong_core::{
+    step, Arena, BallSample, GameState, HeadlessPlugin, PongPlugin, Score, SpectatorClient,
+    SpectatorFrame, SpectatorServer, SPECTATOR_FRAME_SIZE,
+};
+
+fn streaming_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugin::default())
+        .insert_state(GameState::Serving)
+        .insert_resource(SpectatorServer::bind("127.0.0.1:0").unwrap())
+        .add_plugins(PongPlugin);
+    step(&mut app, 1);
+    app
+}
+
+fn connect(app: &App) -> SpectatorClient {
+    let address = app
+        .world()
+        .resource::<SpectatorServer>()
+        .local_addr()
+        .unwrap();
+    SpectatorClient::connect(address).unwrap()
+}
+
+// Avança o jogo até o espectador receber um quadro
+fn next_frame(app: &mut App, client: &mut SpectatorClient) -> SpectatorFrame {
+    for _ in 0..200 {
+        step(app, 1);
+        if let Some(frame) = client.poll() {
+            return frame;
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+    panic!("nenhum quadro recebido");
+}
+
+#[test]
+fn spectator_receives_ball_and_score() {
+    let mut app = streaming_app();
+    let mut client = connect(&app);
+    app.world_mut().resource_mut::<Score>().right = 3;
+    *app.world_mut().resource_mut::<BallSample>() = BallSample {
+        position: Vec2::new(10., -20.),
+        velocity: Vec2::new(-60., 60.),
+    };
+
+    let frame = next_frame(&mut app, &mut client);
+
+    assert_eq!(frame.arena, Arena::default().size);
+    assert_eq!(frame.ball, Vec2::new(10., -20.));
+    assert_eq!(frame.ball_velocity, Vec2::new(-60., 60.));
+    assert_eq!((frame.left, frame.right), (0, 3));
+    assert_eq!(app.world().resource::<SpectatorServer>().spectators(), 1);
+}
+
+#[test]
+fn frames_round_trip_in_a_compact_layout() {
+    let frame = SpectatorFrame {
+        arena: Vec2::new(1920., 1080.),
+        ball: Vec2::new(-12.5, 300.),
+        ball_velocity: Vec2::new(480., -96.),
+        ball_radius: 10.,
+        right_paddle: Vec2::new(910., 42.),
+        left_paddle: Vec2::new(-910., -17.),
+        paddle_size: Vec2::new(15., 75.),
+        left: 7,
+        right: 10,
+        left_sets: 1,
+        right_sets: 2,
+    };
+
+    let bytes = frame.encode();
+
+    assert_eq!(bytes.len(), SPECTATOR_FRAME_SIZE);
+    assert_eq!(SPECTATOR_FRAME_SIZE, 56);
+    assert_eq!(SpectatorFrame::decode(&bytes), Some(frame));
+    assert_eq!(SpectatorFrame::decode(&bytes[1..]), None);
+}
+
+#[test]
+fn frames_are_sent_at_the_spectator_rate() {
+    let mut app = streaming_app();
+    let mut client = connect(&app);
+    next_frame(&mut app, &mut client);
+
+    // Um segundo de jogo a 64 passos por segundo
+    let mut frames = 0;
+    for _ in 0..64 {
+        step(&mut app, 1);
+        thread::sleep(Duration::from_millis(1));
+        if client.poll().is_some() {
+            frames += 1;
+        }
+    }
+
+    assert!((29..=31).contains(&frames), "{frames}");
+}
+
+#[test]
+fn lost_connection_is_reported() {
+    let mut app = streaming_app();
+    let mut client = connect(&app);
+    next_frame(&mut app, &mut client);
+
+    app.world_mut().remove_resource::<SpectatorServer>();
+    drop(app);
+    thread::sleep(Duration::from_millis(10));
+    client.poll();
+
+    assert!(!client.connected());
+}
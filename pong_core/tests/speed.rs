@@ -0,0 +1,102 @@
+use pong_core::{GameConfig, SpeedCurve};
+
+// Velocidades depois de `hits` toques a partir da velocidade de saque
+fn speeds(config: &GameConfig, hits: u32) -> Vec<f32> {
+    let mut speed = config.ball_speed;
+    (0..hits)
+        .map(|hit| {
+            speed = config.next_ball_speed(speed, hit);
+            speed
+        })
+        .collect()
+}
+
+fn config(speed_curve: SpeedCurve) -> GameConfig {
+    GameConfig {
+        ball_speed: 100.,
+        speed_increment: 10.,
+        max_ball_speed: 1000.,
+        speed_curve,
+        ..GameConfig::default()
+    }
+}
+
+#[test]
+fn linear_adds_the_increment() {
+    assert_eq!(
+        speeds(&config(SpeedCurve::Linear), 3),
+        vec![110., 120., 130.]
+    );
+}
+
+#[test]
+fn multiplicative_scales_the_speed() {
+    assert_eq!(
+        speeds(&config(SpeedCurve::Multiplicative(2.)), 3),
+        vec![200., 400., 800.]
+    );
+}
+
+#[test]
+fn logarithmic_increments_shrink() {
+    let speeds = speeds(&config(SpeedCurve::Logarithmic(2.)), 3);
+    assert_eq!(speeds[0], 110.);
+    assert_eq!(speeds[2] - speeds[1], 5.); // Metade do aumento após dois toques
+}
+
+#[test]
+fn capped_stops_at_the_cap() {
+    assert_eq!(
+        speeds(&config(SpeedCurve::Capped(125.)), 4),
+        vec![110., 120., 125., 125.]
+    );
+}
+
+#[test]
+fn no_curve_passes_the_max_ball_speed() {
+    for curve in [SpeedCurve::Linear, SpeedCurve::Multiplicative(3.)] {
+        let speeds = speeds(&config(curve), 200);
+        assert_eq!(*speeds.last().unwrap(), 1000.);
+    }
+}
+
+#[test]
+fn invalid_curves_are_rejected() {
+    for curve in [
+        SpeedCurve::Multiplicative(0.5),
+        SpeedCurve::Logarithmic(0.),
+        SpeedCurve::Capped(50.),
+    ] {
+        assert!(config(curve).validate().is_err(), "{curve:?}");
+    }
+    assert!(config(SpeedCurve::Capped(500.)).validate().is_ok());
+}
+
+#[test]
+fn files_without_the_acceleration_fields_still_load() {
+    let config = config(SpeedCurve::Capped(500.));
+    let text = ron::ser::to_string_pretty(&config, ron::ser::PrettyConfig::default()).unwrap();
+    let old_text: String = text
+        .lines()
+        .filter(|line| {
+            !["speed_curve", "reset_speed_on_score"]
+                .iter()
+                .any(|field| line.contains(field))
+        })
+        .collect();
+
+    let loaded: GameConfig = ron::from_str(&old_text).unwrap();
+    assert_eq!(loaded.speed_curve, SpeedCurve::Linear);
+    assert!(loaded.reset_speed_on_score);
+    assert_eq!(loaded.ball_speed, config.ball_speed);
+}
+
+#[test]
+fn files_with_the_old_paddle_hits_only_field_still_load() {
+    let config = config(SpeedCurve::Linear);
+    let text = ron::ser::to_string_pretty(&config, ron::ser::PrettyConfig::default()).unwrap();
+    let old_text = text.replacen('(', "(\n    paddle_hits_only: true,", 1);
+
+    let loaded: GameConfig = ron::from_str(&old_text).unwrap();
+    assert_eq!(loaded, config);
+}
@@ -0,0 +1,161 @@
+use bevy::prelude::*;
+use pong_core::{
+    step, AiSettings, Ball, CountdownText, Difficulty, DifficultyText, GameState, HeadlessPlugin,
+    PongPlugin, Score, Scored, Scorer,
+};
+
+fn headless_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((HeadlessPlugin::default(), PongPlugin));
+    step(&mut app, 1);
+    app
+}
+
+// Aperta e solta uma tecla, como em um quadro real
+fn tap(app: &mut App, key: KeyCode) {
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(key);
+    step(app, 1);
+    let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keyboard.release(key);
+    keyboard.clear();
+    step(app, 1); // Aplica a troca de estado
+}
+
+fn state(app: &App) -> GameState {
+    *app.world().resource::<State<GameState>>().get()
+}
+
+fn ball_count(app: &mut App) -> usize {
+    let world = app.world_mut();
+    world.query::<&Ball>().iter(world).count()
+}
+
+#[test]
+fn starts_in_the_main_menu_without_arena() {
+    let mut app = headless_app();
+
+    assert_eq!(state(&app), GameState::MainMenu);
+    assert_eq!(ball_count(&mut app), 0);
+}
+
+#[test]
+fn serve_and_pause_keys_walk_through_the_states() {
+    let mut app = headless_app();
+
+    tap(&mut app, KeyCode::Space);
+    assert_eq!(state(&app), GameState::Serving);
+    assert_eq!(ball_count(&mut app), 1);
+
+    tap(&mut app, KeyCode::Space);
+    assert_eq!(state(&app), GameState::Playing);
+
+    tap(&mut app, KeyCode::Escape);
+    assert_eq!(state(&app), GameState::Paused);
+    assert_eq!(ball_count(&mut app), 1); // A arena continua durante a pausa
+
+    tap(&mut app, KeyCode::Escape);
+    assert_eq!(state(&app), GameState::Playing);
+}
+
+#[test]
+fn each_point_returns_to_serving() {
+    let mut app = headless_app();
+    tap(&mut app, KeyCode::Space);
+    tap(&mut app, KeyCode::Space);
+
+    app.world_mut().send_event(Scored(Scorer::Left));
+    step(&mut app, 2);
+
+    assert_eq!(state(&app), GameState::Serving);
+    assert_eq!(app.world().resource::<Score>().left, 1);
+}
+
+#[test]
+fn restart_after_game_over_respawns_the_arena() {
+    let mut app = headless_app();
+    tap(&mut app, KeyCode::Space);
+    app.world_mut().resource_mut::<Score>().right = 3;
+
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::GameOver);
+    step(&mut app, 1);
+    assert_eq!(ball_count(&mut app), 0);
+
+    tap(&mut app, KeyCode::Space);
+    assert_eq!(state(&app), GameState::Serving);
+    assert_eq!(ball_count(&mut app), 1);
+    let score = app.world().resource::<Score>();
+    assert_eq!((score.left, score.right), (0, 0));
+}
+
+#[test]
+fn serve_countdown_shows_seconds_and_serves_when_it_ends() {
+    let mut app = headless_app();
+    tap(&mut app, KeyCode::Space);
+    assert_eq!(state(&app), GameState::Serving);
+
+    let mut countdown = app
+        .world_mut()
+        .query_filtered::<&Text, With<CountdownText>>();
+    assert_eq!(countdown.single(app.world()).0, "3");
+
+    // Pouco mais de um segundo depois faltam dois
+    step(&mut app, 70);
+    assert_eq!(countdown.single(app.world()).0, "2");
+
+    step(&mut app, 2 * 64);
+    assert_eq!(state(&app), GameState::Playing);
+    assert_eq!(countdown.iter(app.world()).count(), 0); // O texto some com o saque
+}
+
+#[test]
+fn pausing_during_the_countdown_returns_to_the_serve() {
+    let mut app = headless_app();
+    tap(&mut app, KeyCode::Space);
+
+    tap(&mut app, KeyCode::Escape);
+    assert_eq!(state(&app), GameState::Paused);
+    tap(&mut app, KeyCode::Escape);
+    assert_eq!(state(&app), GameState::Serving);
+
+    // A contagem recomeça e termina no saque
+    step(&mut app, 3 * 64 + 2);
+    assert_eq!(state(&app), GameState::Playing);
+}
+
+#[test]
+fn main_menu_chooses_the_ai_difficulty() {
+    let mut app = headless_app();
+    let mut text = app
+        .world_mut()
+        .query_filtered::<&Text, With<DifficultyText>>();
+    assert_eq!(
+        text.single(app.world()).0,
+        "IA: < Normal >  (para cima ou para baixo muda)"
+    );
+
+    tap(&mut app, KeyCode::ArrowUp);
+    tap(&mut app, KeyCode::ArrowUp); // Já está no mais difícil
+    assert_eq!(*app.world().resource::<Difficulty>(), Difficulty::Hard);
+    assert_eq!(
+        *app.world().resource::<AiSettings>(),
+        AiSettings::from(Difficulty::Hard)
+    );
+    assert!(text.single(app.world()).0.contains("Difícil"));
+
+    tap(&mut app, KeyCode::ArrowDown);
+    tap(&mut app, KeyCode::ArrowDown);
+    assert_eq!(*app.world().resource::<Difficulty>(), Difficulty::Easy);
+
+    // A escolha vale para a partida; no jogo as setas só movem a raquete
+    tap(&mut app, KeyCode::Space);
+    tap(&mut app, KeyCode::ArrowUp);
+    assert_eq!(*app.world().resource::<Difficulty>(), Difficulty::Easy);
+    assert_eq!(
+        *app.world().resource::<AiSettings>(),
+        AiSettings::from(Difficulty::Easy)
+    );
+}
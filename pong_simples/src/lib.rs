@@ -0,0 +1,642 @@
+use bevy::{
+    math::bounding::{Aabb2d, BoundingCircle, BoundingVolume, IntersectsVolume},
+    prelude::*,
+};
+use pong_core::{
+    paddle_bounce, paddles_can_move, random_serve_direction, spawn_paddles, store_match_geometry,
+    Ai, AiController, AiSettings, AiView, Arena, Ball, BallHit, BallSample, GameConfig, GameRng,
+    GameState, HitKind, InGame, MatchGeometry, Paddle, PlayerActions, PlayerSlot, PongPlugin,
+    Scored, Scorer, Server,
+};
+
+mod netplay;
+
+pub use netplay::{
+    advance_net_session, end_net_match, mark_online_match, NetInput, NetSession, NetSetup,
+    NetState, MAX_PREDICTION,
+};
+
+// Velocidades e tamanhos vêm do `GameConfig`; os valores padrão do `pong_core` são os desta versão
+pub const MAX_BOUNCES_PER_STEP: usize = 4; // Reflexões tratadas por passo fixo na varredura da bola
+
+// Componente para as barreiras superior e inferior
+#[derive(Component)]
+#[require(Position, Shape)]
+pub struct Gutter;
+
+// Componente para armazenar a posição de uma entidade
+#[derive(Component, Default)]
+pub struct Position(pub Vec2);
+
+// Componente com a posição no passo fixo anterior, usada para interpolar o desenho
+#[derive(Component, Default)]
+pub struct PreviousPosition(pub Vec2);
+
+// Componente para armazenar a velocidade de uma entidade
+#[derive(Component, Default)]
+pub struct Velocity(pub Vec2);
+
+// Componente para armazenar o tamanho/forma de uma entidade
+#[derive(Component, Default)]
+pub struct Shape(pub Vec2);
+
+// Enum para identificar o tipo de colisão
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Collision {
+    Left,   // Colisão no lado esquerdo
+    Right,  // Colisão no lado direito
+    Top,    // Colisão no topo
+    Bottom, // Colisão na parte inferior
+}
+
+// Raquete da IA, separada da bola para consultar as duas velocidades ao mesmo tempo
+type AiPaddle = (With<Ai>, Without<Ball>);
+
+// Sistema para mover a raquete da IA conforme a dificuldade escolhida
+pub fn move_ai(
+    mut ai: Query<(&mut AiController, &mut Velocity, &Position), AiPaddle>,
+    ball: Query<(&Position, &Velocity, &Shape), With<Ball>>,
+    settings: Res<AiSettings>,
+    arena: Res<Arena>,
+    geometry: Res<MatchGeometry>,
+    time: Res<Time>,
+    mut rng: ResMut<GameRng>,
+) {
+    if let Ok((mut controller, mut velocity, position)) = ai.get_single_mut() {
+        if let Ok((ball_position, ball_velocity, ball_shape)) = ball.get_single() {
+            // Faixa em que o centro da bola anda entre as barreiras
+            let edge = arena.half_size().y - geometry.wall_thickness - ball_shape.0.x;
+            let view = AiView {
+                paddle: position.0,
+                ball: ball_position.0,
+                ball_velocity: ball_velocity.0,
+                floor: -edge,
+                ceiling: edge,
+            };
+            velocity.0.y = controller.steer(&settings, &view, time.delta_secs(), &mut *rng);
+        }
+    }
+}
+
+// Sistema para detectar quando alguém marca ponto
+pub fn detect_scoring(
+    mut ball: Query<&mut Position, With<Ball>>,
+    arena: Res<Arena>,
+    mut events: EventWriter<Scored>,
+) {
+    let arena_width = arena.width();
+
+    if let Ok(ball) = ball.get_single_mut() {
+        // Se a bola saiu pela direita, o lado esquerdo marcou ponto
+        if ball.0.x > arena_width / 2. {
+            events.send(Scored(Scorer::Left));
+        // Se a bola saiu pela esquerda, o lado direito marcou ponto
+        } else if ball.0.x < -arena_width / 2. {
+            events.send(Scored(Scorer::Right));
+        }
+    }
+}
+
+// Sistema para recolocar a bola parada no centro após alguém marcar ponto; ela espera ali pelo saque
+pub fn reset_ball(
+    mut ball: Query<(&mut Position, &mut PreviousPosition, &mut Velocity), With<Ball>>,
+    mut events: EventReader<Scored>,
+) {
+    for _ in events.read() {
+        if let Ok((mut position, mut previous, mut velocity)) = ball.get_single_mut() {
+            position.0 = Vec2::ZERO; // Centro da tela
+            previous.0 = Vec2::ZERO; // Evita interpolar o teletransporte até o centro
+            velocity.0 = Vec2::ZERO; // Só o saque põe a bola em jogo de novo
+        }
+    }
+}
+
+// Sistema para sacar a bola com um ângulo sorteado, em direção ao adversário de quem saca
+pub fn launch_ball(
+    mut ball: Query<&mut Velocity, With<Ball>>,
+    server: Res<Server>,
+    mut rng: ResMut<GameRng>,
+) {
+    if let Ok(mut velocity) = ball.get_single_mut() {
+        // Mantém a rapidez do antigo saque na diagonal
+        velocity.0 = random_serve_direction(server.0, &mut *rng) * std::f32::consts::SQRT_2;
+    }
+}
+
+// Sistema para mover cada raquete controlada por uma pessoa conforme as ações do seu jogador;
+// os eixos analógicos dão velocidade proporcional à inclinação
+pub fn handle_player_input(
+    actions: Res<PlayerActions>,
+    mut paddles: Query<(&PlayerSlot, &mut Velocity)>,
+) {
+    for (slot, mut velocity) in &mut paddles {
+        velocity.0.y = actions.get(slot.0).movement;
+    }
+}
+
+// Sistema para criar as barreiras superior e inferior
+pub fn spawn_gutters(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    arena: Res<Arena>,
+    geometry: Res<MatchGeometry>,
+) {
+    let arena_width = arena.width();
+    let arena_height = arena.height();
+    let gutter_height = geometry.wall_thickness;
+
+    // Calcula a posição das barreiras
+    let top_gutter_y = arena_height / 2. - gutter_height / 2.;
+    let bottom_gutter_y = -arena_height / 2. + gutter_height / 2.;
+
+    let shape = Rectangle::from_size(Vec2::new(arena_width, gutter_height));
+    let color = Color::srgb(0., 0., 0.); // Cor preta
+
+    // Podemos compartilhar as meshes entre as barreiras clonando-as
+    let mesh_handle = meshes.add(shape);
+    let material_handle = materials.add(color);
+
+    // Cria a barreira superior
+    commands.spawn((
+        Gutter,
+        Shape(shape.size()),
+        Position(Vec2::new(0., top_gutter_y)),
+        Mesh2d(mesh_handle.clone()),
+        MeshMaterial2d(material_handle.clone()),
+        StateScoped(InGame),
+    ));
+
+    // Cria a barreira inferior
+    commands.spawn((
+        Gutter,
+        Shape(shape.size()),
+        Position(Vec2::new(0., bottom_gutter_y)),
+        Mesh2d(mesh_handle.clone()),
+        MeshMaterial2d(material_handle.clone()),
+        StateScoped(InGame),
+    ));
+}
+
+// Sistema para atualizar a posição visual com base na posição lógica,
+// interpolando entre os dois últimos passos fixos para que o movimento continue suave
+pub fn project_positions(
+    mut positionables: Query<(&mut Transform, &Position, Option<&PreviousPosition>)>,
+    time: Res<Time<Fixed>>,
+) {
+    let alpha = time.overstep_fraction(); // Fração do próximo passo fixo já decorrida
+
+    for (mut transform, position, previous) in &mut positionables {
+        let translation = match previous {
+            Some(previous) => previous.0.lerp(position.0, alpha),
+            None => position.0,
+        };
+        transform.translation = translation.extend(0.); // Converte Vec2 para Vec3 (z=0)
+    }
+}
+
+// Sistema que guarda as posições no início de cada passo fixo
+pub fn store_previous_positions(mut positionables: Query<(&mut PreviousPosition, &Position)>) {
+    for (mut previous, position) in &mut positionables {
+        previous.0 = position.0;
+    }
+}
+
+// Obstáculos da bola: barreiras e raquetes (estas com a própria velocidade)
+type Obstacle = (
+    &'static Position,
+    &'static Shape,
+    Option<&'static Velocity>,
+    Has<Paddle>,
+);
+
+// Obstáculo da bola em um passo: uma barreira ou uma raquete (esta com a própria velocidade)
+#[derive(Debug, Clone, Copy)]
+pub struct ObstacleBody {
+    pub position: Vec2, // Centro do obstáculo
+    pub size: Vec2,     // Tamanho do obstáculo
+    pub velocity: Vec2, // Direção do movimento da raquete; zero nas barreiras
+    pub paddle: bool,   // Se é uma raquete
+}
+
+impl ObstacleBody {
+    // Toque da bola neste obstáculo, para os efeitos sonoros e visuais
+    pub fn hit(&self, ball_position: Vec2) -> BallHit {
+        let kind = if self.paddle {
+            HitKind::Paddle
+        } else {
+            HitKind::Wall
+        };
+        BallHit::new(kind, ball_position)
+    }
+}
+
+// Copia os obstáculos da consulta para o passo puro da bola
+fn obstacle_bodies(obstacles: &Query<Obstacle, Without<Ball>>) -> Vec<ObstacleBody> {
+    obstacles
+        .iter()
+        .map(|(position, shape, velocity, paddle)| ObstacleBody {
+            position: position.0,
+            size: shape.0,
+            velocity: velocity.map_or(Vec2::ZERO, |velocity| velocity.0),
+            paddle,
+        })
+        .collect()
+}
+
+// Avança a bola por um passo de `delta` segundos, varrendo o trajeto contra os obstáculos para
+// que bolas rápidas não os atravessem (detecção contínua de colisão). Não depende do ECS, então
+// serve tanto para `move_ball` quanto para simular passos de novo no modo online.
+// Devolve os toques da bola no passo, na ordem em que aconteceram.
+pub fn step_ball(
+    position: &mut Vec2,
+    velocity: &mut Vec2,
+    radius: f32,
+    obstacles: &[ObstacleBody],
+    config: &GameConfig,
+    delta: f32,
+) -> Vec<BallHit> {
+    // Deslocamento do passo, limitado pela velocidade máxima da bola
+    let mut remaining =
+        (*velocity * config.ball_speed).clamp_length_max(config.max_ball_speed) * delta;
+    let mut hits = Vec::new();
+
+    for _ in 0..MAX_BOUNCES_PER_STEP {
+        // Primeiro obstáculo atingido ao longo do trajeto restante
+        let hit = obstacles
+            .iter()
+            .filter_map(|obstacle| {
+                sweep_circle_aabb(
+                    *position,
+                    radius,
+                    remaining,
+                    Aabb2d::new(obstacle.position, obstacle.size / 2.),
+                )
+                .map(|(time_of_impact, collision)| (time_of_impact, collision, obstacle))
+            })
+            .min_by(|(a, ..), (b, ..)| a.total_cmp(b));
+
+        let Some((time_of_impact, collision, obstacle)) = hit else {
+            *position += remaining; // Caminho livre até o fim do passo
+            break;
+        };
+
+        // Avança até o ponto de contato e segue o restante do trajeto na nova direção
+        *position += remaining * time_of_impact;
+        let distance = remaining.length() * (1. - time_of_impact);
+        *velocity = deflect(*velocity, *position, collision, obstacle, config);
+        remaining = velocity.normalize_or_zero() * distance;
+        hits.push(obstacle.hit(*position));
+    }
+
+    hits
+}
+
+// Sistema para mover a bola com `step_ball`, avisando cada toque para os efeitos sonoros
+pub fn move_ball(
+    mut ball: Query<(&mut Position, &mut Velocity, &Shape), With<Ball>>,
+    obstacles: Query<Obstacle, Without<Ball>>,
+    config: Res<GameConfig>,
+    time: Res<Time>,
+    mut hits: EventWriter<BallHit>,
+) {
+    if let Ok((mut position, mut velocity, shape)) = ball.get_single_mut() {
+        let touches = step_ball(
+            &mut position.0,
+            &mut velocity.0,
+            shape.0.x,
+            &obstacle_bodies(&obstacles),
+            &config,
+            time.delta_secs(),
+        );
+        hits.send_batch(touches);
+    }
+}
+
+// Avança uma raquete por um passo de `delta` segundos, sem passar das barreiras
+pub fn step_paddle(
+    position: &mut Vec2,
+    velocity: Vec2,
+    arena: &Arena,
+    geometry: &MatchGeometry,
+    paddle_speed: f32,
+    delta: f32,
+) {
+    let new_position = *position + velocity * paddle_speed * delta;
+    // Verifica se a raquete não ultrapassará os limites da arena
+    if new_position.y.abs() < geometry.paddle_limit(arena) {
+        *position = new_position;
+    }
+}
+
+// Sistema para mover as raquetes com `step_paddle`
+pub fn move_paddles(
+    mut paddle: Query<(&mut Position, &Velocity), With<Paddle>>,
+    arena: Res<Arena>,
+    geometry: Res<MatchGeometry>,
+    config: Res<GameConfig>,
+    time: Res<Time>,
+) {
+    for (mut position, velocity) in &mut paddle {
+        step_paddle(
+            &mut position.0,
+            velocity.0,
+            &arena,
+            &geometry,
+            config.paddle_speed,
+            time.delta_secs(),
+        );
+    }
+}
+
+// Função auxiliar para detectar colisões entre a bola e outros objetos
+pub fn collide_with_side(ball: BoundingCircle, wall: Aabb2d) -> Option<Collision> {
+    if !ball.intersects(&wall) {
+        return None; // Sem colisão
+    }
+
+    let closest = wall.closest_point(ball.center());
+    let offset = ball.center() - closest;
+
+    // Determina o lado da colisão com base no maior deslocamento
+    let side = if offset.x.abs() > offset.y.abs() {
+        if offset.x < 0. {
+            Collision::Left
+        } else {
+            Collision::Right
+        }
+    } else if offset.y > 0. {
+        Collision::Top
+    } else {
+        Collision::Bottom
+    };
+
+    Some(side)
+}
+
+// Calcula quando uma bola que percorre `displacement` toca a caixa, devolvendo a fração do
+// trajeto até o contato (0 a 1) e o lado atingido. A caixa é expandida pelo raio da bola,
+// aproximando os cantos arredondados por cantos retos. Bolas já sobrepostas são ignoradas e
+// ficam a cargo de `handle_collisions`.
+pub fn sweep_circle_aabb(
+    center: Vec2,
+    radius: f32,
+    displacement: Vec2,
+    wall: Aabb2d,
+) -> Option<(f32, Collision)> {
+    let min = wall.min - radius;
+    let max = wall.max + radius;
+
+    if center.cmpgt(min).all() && center.cmplt(max).all() {
+        return None; // Já sobreposta
+    }
+
+    // Intervalo do trajeto dentro de cada faixa (eixo) da caixa expandida
+    let mut entry = Vec2::splat(f32::NEG_INFINITY);
+    let mut exit = Vec2::splat(f32::INFINITY);
+    for axis in 0..2 {
+        if displacement[axis] == 0. {
+            if center[axis] <= min[axis] || center[axis] >= max[axis] {
+                return None; // Parada nesse eixo e fora da faixa
+            }
+        } else {
+            let t1 = (min[axis] - center[axis]) / displacement[axis];
+            let t2 = (max[axis] - center[axis]) / displacement[axis];
+            entry[axis] = t1.min(t2);
+            exit[axis] = t1.max(t2);
+        }
+    }
+
+    let time_of_impact = entry.max_element();
+    if time_of_impact > exit.min_element() || !(0. ..=1.).contains(&time_of_impact) {
+        return None; // Não alcança a caixa neste passo
+    }
+
+    // O último eixo a entrar na caixa define o lado atingido
+    let side = if entry.x > entry.y {
+        if displacement.x > 0. {
+            Collision::Left
+        } else {
+            Collision::Right
+        }
+    } else if displacement.y > 0. {
+        Collision::Bottom
+    } else {
+        Collision::Top
+    };
+
+    Some((time_of_impact, side))
+}
+
+// Reflete um vetor para longe do lado atingido. Usa o valor absoluto em vez de inverter o
+// sinal para que contatos repetidos no mesmo obstáculo não desfaçam a reflexão.
+pub fn bounce(vector: &mut Vec2, collision: Collision) {
+    match collision {
+        Collision::Left => vector.x = -vector.x.abs(), // Bola à esquerda volta para a esquerda
+        Collision::Right => vector.x = vector.x.abs(), // Bola à direita volta para a direita
+        Collision::Top => vector.y = vector.y.abs(),   // Bola acima volta para cima
+        Collision::Bottom => vector.y = -vector.y.abs(), // Bola abaixo volta para baixo
+    }
+}
+
+// Velocidade da bola após tocar um obstáculo. Nas faces laterais das raquetes o ângulo de
+// saída depende do ponto de contato e do movimento da raquete; nos demais casos a bola reflete.
+fn deflect(
+    velocity: Vec2,
+    ball_position: Vec2,
+    collision: Collision,
+    obstacle: &ObstacleBody,
+    config: &GameConfig,
+) -> Vec2 {
+    let away = match collision {
+        Collision::Left => -1.,
+        Collision::Right => 1.,
+        Collision::Top | Collision::Bottom => 0.,
+    };
+
+    if obstacle.paddle && away != 0. {
+        // As velocidades da bola e da raquete são direções; converte para unidades por segundo
+        paddle_bounce(
+            velocity * config.ball_speed,
+            away,
+            ball_position.y - obstacle.position.y,
+            obstacle.size.y / 2.,
+            obstacle.velocity.y * config.paddle_speed,
+            config,
+        ) / config.ball_speed
+    } else {
+        let mut velocity = velocity;
+        bounce(&mut velocity, collision);
+        velocity
+    }
+}
+
+// Reflete a bola que já se sobrepõe a um obstáculo, como quando uma raquete avança sobre ela.
+// Devolve os toques da bola que ainda ia contra o obstáculo; a sobreposição pode durar
+// vários passos e só o primeiro conta como toque
+pub fn resolve_overlaps(
+    velocity: &mut Vec2,
+    position: Vec2,
+    radius: f32,
+    obstacles: &[ObstacleBody],
+    config: &GameConfig,
+) -> Vec<BallHit> {
+    let mut hits = Vec::new();
+    for obstacle in obstacles {
+        // Verifica colisão entre a bola e o objeto
+        if let Some(collision) = collide_with_side(
+            BoundingCircle::new(position, radius),
+            Aabb2d::new(obstacle.position, obstacle.size / 2.0),
+        ) {
+            if approaching(*velocity, collision) {
+                hits.push(obstacle.hit(position));
+            }
+            // Reflete a direção da bola baseado no tipo de colisão
+            *velocity = deflect(*velocity, position, collision, obstacle, config);
+        }
+    }
+
+    hits
+}
+
+// Se a bola ainda vai contra o lado do obstáculo em que encostou
+fn approaching(velocity: Vec2, collision: Collision) -> bool {
+    match collision {
+        Collision::Left => velocity.x > 0., // Bola à esquerda indo para a direita
+        Collision::Right => velocity.x < 0., // Bola à direita indo para a esquerda
+        Collision::Top => velocity.y < 0.,  // Bola acima descendo
+        Collision::Bottom => velocity.y > 0., // Bola abaixo subindo
+    }
+}
+
+// Sistema para tratar colisões da bola que já se sobrepõe a um obstáculo com `resolve_overlaps`
+pub fn handle_collisions(
+    mut ball: Query<(&mut Velocity, &Position, &Shape), With<Ball>>,
+    other_things: Query<Obstacle, Without<Ball>>,
+    config: Res<GameConfig>,
+    mut hits: EventWriter<BallHit>,
+) {
+    if let Ok((mut ball_velocity, ball_position, ball_shape)) = ball.get_single_mut() {
+        let touches = resolve_overlaps(
+            &mut ball_velocity.0,
+            ball_position.0,
+            ball_shape.0.x,
+            &obstacle_bodies(&other_things),
+            &config,
+        );
+        hits.send_batch(touches);
+    }
+}
+
+// Observador que adiciona a física própria desta versão à bola criada pelo `PongPlugin`
+pub fn add_ball_physics(
+    trigger: Trigger<OnAdd, Ball>,
+    mut commands: Commands,
+    geometry: Res<MatchGeometry>,
+) {
+    commands.entity(trigger.entity()).insert((
+        Position::default(),
+        PreviousPosition::default(),
+        Velocity::default(),                      // Parada até o saque
+        Shape(Vec2::splat(geometry.ball_radius)), // Tamanho da bola
+    ));
+}
+
+// Observador que adiciona a física às raquetes, partindo da posição em que foram criadas
+pub fn add_paddle_physics(
+    trigger: Trigger<OnAdd, Paddle>,
+    mut commands: Commands,
+    transforms: Query<&Transform>,
+    geometry: Res<MatchGeometry>,
+) {
+    let position = transforms
+        .get(trigger.entity())
+        .map(|transform| transform.translation.truncate())
+        .unwrap_or_default();
+
+    commands.entity(trigger.entity()).insert((
+        Position(position),
+        PreviousPosition(position),
+        Velocity::default(),
+        Shape(geometry.paddle_size),
+    ));
+}
+
+// Sistema que copia a bola para o `BallSample` ao fim de cada passo fixo; a velocidade
+// guardada é uma direção, então é convertida para unidades por segundo
+pub fn sample_ball(
+    ball: Query<(&Position, &Velocity), With<Ball>>,
+    mut sample: ResMut<BallSample>,
+    config: Res<GameConfig>,
+) {
+    if let Ok((position, velocity)) = ball.get_single() {
+        *sample = BallSample {
+            position: position.0,
+            velocity: velocity.0 * config.ball_speed,
+        };
+    }
+}
+
+// Plugin com as regras do pong simples: física própria, pontuação, entrada do jogador e IA.
+// A bola só anda em `GameState::Playing`; no saque ela espera parada no centro.
+// Funciona tanto com o `DefaultPlugins` quanto com o `HeadlessPlugin`.
+pub struct PongSimplesPlugin;
+
+impl Plugin for PongSimplesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(PongPlugin) // Base comum: configuração, arena, câmera, bola e placar
+            .add_observer(add_ball_physics) // Física da bola
+            .add_observer(add_paddle_physics) // Física das raquetes
+            .add_systems(
+                OnEnter(InGame), // Sistemas executados no início de cada partida
+                (
+                    spawn_paddles, // Cria as raquetes
+                    spawn_gutters, // Cria as barreiras superior e inferior
+                )
+                    .after(store_match_geometry), // Com as medidas fixadas para a partida
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Serving,
+                    entered: GameState::Playing,
+                }, // Saque
+                launch_ball,
+            )
+            .add_systems(
+                FixedFirst,               // Início de cada passo fixo
+                store_previous_positions, // Guarda as posições para a interpolação
+            )
+            .add_systems(
+                FixedUpdate, // Sistemas executados a cada passo fixo, independentes da taxa de quadros
+                (
+                    // As raquetes se movem também enquanto a bola espera o saque
+                    (
+                        move_ai,                                                // Move a IA
+                        handle_player_input, // Processa entrada do jogador
+                        move_paddles.after(move_ai).after(handle_player_input), // Move as raquetes
+                    )
+                        .run_if(paddles_can_move),
+                    (
+                        move_ball,                               // Move a bola
+                        handle_collisions.after(move_ball),      // Trata colisões
+                        detect_scoring.after(handle_collisions), // Detecta pontuação
+                        reset_ball.after(detect_scoring),        // Reseta a bola após pontuação
+                    )
+                        .run_if(in_state(GameState::Playing)),
+                )
+                    .run_if(not(resource_exists::<NetSession>)), // No modo online quem simula é a sessão
+            )
+            .add_systems(
+                FixedUpdate,
+                (advance_net_session, end_net_match)
+                    .chain()
+                    .run_if(resource_exists::<NetSession>), // Partida online com rollback
+            )
+            .add_systems(
+                PreUpdate,
+                mark_online_match.run_if(resource_added::<NetSession>),
+            )
+            .add_systems(FixedPostUpdate, sample_ball) // Bola vista pela gravação e pelos espectadores
+            .add_systems(Update, project_positions); // Atualiza posições visuais a cada quadro
+    }
+}
@@ -0,0 +1,506 @@
+use std::{
+    io,
+    net::{SocketAddr, UdpSocket},
+};
+
+use bevy::prelude::*;
+use pong_core::{
+    ball_checksum, random_serve_direction, Arena, Ball, GameConfig, GameState, MatchGeometry,
+    MatchRules, MatchWon, OnlineMatch, Paddle, Player, PlayerActions, Score, Scorer,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{resolve_overlaps, step_ball, step_paddle, ObstacleBody, Position, Velocity};
+
+// Passos que um lado pode simular à frente da última entrada recebida do outro; além disso
+// ele espera, para que uma correção nunca precise refazer mais do que isso
+pub const MAX_PREDICTION: u32 = 8;
+const MAX_PACKET_INPUTS: usize = 64; // Entradas reenviadas em cada pacote, contra perdas do UDP
+const MAGIC: &[u8; 2] = b"PG"; // Identifica os pacotes do jogo
+
+// Entrada de um jogador em um passo: o movimento da raquete quantizado em um byte, para que os
+// dois lados simulem exatamente com o mesmo valor
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NetInput(pub i8);
+
+impl NetInput {
+    pub fn from_movement(movement: f32) -> Self {
+        Self((movement.clamp(-1., 1.) * 127.).round() as i8)
+    }
+
+    pub fn movement(self) -> f32 {
+        self.0 as f32 / 127.
+    }
+}
+
+// Parâmetros fixos da partida online, iguais nos dois lados
+#[derive(Clone)]
+pub struct NetSetup {
+    pub arena: Arena,
+    pub config: GameConfig,
+    pub rules: MatchRules,
+    pub delta: f32, // Duração de cada passo, em segundos
+}
+
+impl Default for NetSetup {
+    fn default() -> Self {
+        Self {
+            arena: Arena::default(),
+            config: GameConfig::default(),
+            rules: MatchRules::default(),
+            delta: Time::<Fixed>::default().timestep().as_secs_f32(),
+        }
+    }
+}
+
+impl NetSetup {
+    // Passos de espera antes de cada saque
+    fn serve_steps(&self) -> u32 {
+        (self.config.serve_delay / self.delta).round() as u32
+    }
+
+    // Barreiras superior e inferior e as duas raquetes
+    fn obstacles(&self, state: &NetState) -> [ObstacleBody; 4] {
+        let gutter_height = self.config.wall_thickness;
+        let gutter_y = self.arena.height() / 2. - gutter_height / 2.;
+        let gutter = |y| ObstacleBody {
+            position: Vec2::new(0., y),
+            size: Vec2::new(self.arena.width(), gutter_height),
+            velocity: Vec2::ZERO,
+            paddle: false,
+        };
+        let paddle = |index: usize| ObstacleBody {
+            position: state.paddles[index],
+            size: self.config.paddle_size(),
+            velocity: Vec2::new(0., state.paddle_movement[index]),
+            paddle: true,
+        };
+        [gutter(gutter_y), gutter(-gutter_y), paddle(0), paddle(1)]
+    }
+}
+
+// Estado completo da partida online. Tudo o que influencia os próximos passos fica aqui,
+// inclusive o gerador aleatório, para que voltar a um estado guardado e simular de novo dê
+// sempre o mesmo resultado
+#[derive(Clone)]
+pub struct NetState {
+    pub ball: Vec2,                // Posição da bola
+    pub ball_velocity: Vec2,       // Direção da bola, como no `Velocity` do pong simples
+    pub paddles: [Vec2; 2],        // Raquetes da direita (jogador 0) e da esquerda (jogador 1)
+    pub paddle_movement: [f32; 2], // Movimento de cada raquete no último passo
+    pub score: [u32; 2],           // Pontos da direita e da esquerda no set atual
+    pub sets: [u32; 2],            // Sets vencidos pela direita e pela esquerda
+    pub winner: Option<Scorer>,    // Vencedor da partida, quando ela acaba
+    pub serve_steps: u32,          // Passos até o próximo saque
+    pub server: Scorer,            // Lado que faz o próximo saque
+    rng: StdRng,                   // Gerador dos ângulos de saque
+}
+
+impl NetState {
+    pub fn new(seed: u64, setup: &NetSetup) -> Self {
+        let paddle_x = setup.arena.half_size().x - setup.config.paddle_padding;
+        Self {
+            ball: Vec2::ZERO,
+            ball_velocity: Vec2::ZERO,
+            paddles: [Vec2::new(paddle_x, 0.), Vec2::new(-paddle_x, 0.)],
+            paddle_movement: [0.; 2],
+            score: [0; 2],
+            sets: [0; 2],
+            winner: None,
+            serve_steps: setup.serve_steps(),
+            server: Scorer::Right,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    // Avança um passo com as entradas dos dois jogadores, com os mesmos passos puros do jogo local
+    pub fn advance(&mut self, inputs: [NetInput; 2], setup: &NetSetup) {
+        // Depois do fim da partida tudo fica parado no placar final
+        if self.winner.is_some() {
+            return;
+        }
+
+        for (index, input) in inputs.into_iter().enumerate() {
+            self.paddle_movement[index] = input.movement();
+            step_paddle(
+                &mut self.paddles[index],
+                Vec2::new(0., input.movement()),
+                &setup.arena,
+                &MatchGeometry::new(&setup.config),
+                setup.config.paddle_speed,
+                setup.delta,
+            );
+        }
+
+        // A bola espera parada no centro até o fim da contagem do saque
+        if self.serve_steps > 0 {
+            self.serve_steps -= 1;
+            if self.serve_steps == 0 {
+                self.ball_velocity =
+                    random_serve_direction(self.server, &mut self.rng) * std::f32::consts::SQRT_2;
+            }
+            return;
+        }
+
+        let obstacles = setup.obstacles(self);
+        let radius = setup.config.ball_radius;
+        step_ball(
+            &mut self.ball,
+            &mut self.ball_velocity,
+            radius,
+            &obstacles,
+            &setup.config,
+            setup.delta,
+        );
+        resolve_overlaps(
+            &mut self.ball_velocity,
+            self.ball,
+            radius,
+            &obstacles,
+            &setup.config,
+        );
+
+        let half_width = setup.arena.half_size().x;
+        let scorer = if self.ball.x > half_width {
+            Scorer::Left
+        } else if self.ball.x < -half_width {
+            Scorer::Right
+        } else {
+            return;
+        };
+        let side = match scorer {
+            Scorer::Right => 0,
+            Scorer::Left => 1,
+        };
+        self.score[side] += 1;
+        self.server = setup.rules.next_server(scorer, self.server);
+        self.ball = Vec2::ZERO;
+        self.ball_velocity = Vec2::ZERO;
+        self.serve_steps = setup.serve_steps();
+
+        // Fecha o set e a partida com as mesmas regras do `apply_match_rules`
+        if setup
+            .rules
+            .set_winner(self.score[1], self.score[0])
+            .is_some()
+        {
+            self.sets[side] += 1;
+            if self.sets[side] >= setup.rules.sets_to_win() {
+                self.winner = Some(scorer); // Mantém os pontos do último set para a tela de fim de jogo
+            } else {
+                self.score = [0; 2];
+            }
+        }
+    }
+
+    // Resumo do estado, para conferir que os dois lados chegaram ao mesmo resultado
+    pub fn checksum(&self) -> u32 {
+        ball_checksum(self.ball, self.ball_velocity)
+            ^ ball_checksum(self.paddles[0], self.paddles[1]).rotate_left(16)
+    }
+}
+
+// Sessão online com rollback sobre UDP, no estilo do GGRS. Cada lado simula sem esperar pelo
+// outro, prevendo que a entrada do adversário repete a última recebida. Quando a entrada real
+// chega e difere da prevista, a sessão volta ao estado guardado daquele passo e simula de novo
+// até o passo atual. O jogador 0 controla a raquete da direita e escolhe a semente da partida;
+// o jogador 1 controla a da esquerda e começa ao receber o primeiro pacote do jogador 0.
+#[derive(Resource)]
+pub struct NetSession {
+    socket: UdpSocket,               // Socket local, sem bloqueio
+    peer: SocketAddr,                // Endereço do outro jogador
+    player: usize,                   // Índice do jogador local (0 ou 1)
+    seed: u64,                       // Semente da partida, enviada em todos os pacotes
+    setup: NetSetup,                 // Parâmetros da partida
+    state: Option<NetState>,         // Estado atual; vazio até o jogador 1 conhecer a semente
+    frame: u32,                      // Próximo passo a simular
+    local: Vec<NetInput>,            // Entradas locais de cada passo
+    remote: Vec<NetInput>,           // Entradas confirmadas do adversário
+    used_remote: Vec<NetInput>,      // Entrada do adversário usada em cada passo simulado
+    snapshots: Vec<(u32, NetState)>, // Estado no início de cada passo ainda não confirmado
+    peer_ack: u32,                   // Entradas locais que o adversário já confirmou
+    rollbacks: u32,                  // Quantas correções foram feitas
+}
+
+impl NetSession {
+    // Abre o socket local e prepara a sessão; a semente só é usada pelo jogador 0
+    pub fn new(
+        local: SocketAddr,
+        peer: SocketAddr,
+        player: usize,
+        seed: u64,
+        setup: NetSetup,
+    ) -> io::Result<Self> {
+        let socket = UdpSocket::bind(local)?;
+        socket.set_nonblocking(true)?;
+        let state = (player == 0).then(|| NetState::new(seed, &setup));
+        Ok(Self {
+            socket,
+            peer,
+            player,
+            seed,
+            setup,
+            state,
+            frame: 0,
+            local: Vec::new(),
+            remote: Vec::new(),
+            used_remote: Vec::new(),
+            snapshots: Vec::new(),
+            peer_ack: 0,
+            rollbacks: 0,
+        })
+    }
+
+    // Endereço do socket local, útil quando a porta é escolhida pelo sistema
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    pub fn player(&self) -> usize {
+        self.player
+    }
+
+    // Próximo passo a simular
+    pub fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    // Passos em que as entradas dos dois jogadores já são conhecidas
+    pub fn confirmed_frame(&self) -> u32 {
+        (self.remote.len() as u32).min(self.frame)
+    }
+
+    // Estado atual da partida, se ela já começou
+    pub fn state(&self) -> Option<&NetState> {
+        self.state.as_ref()
+    }
+
+    // Estado ao fim dos passos já confirmados, que nenhuma correção muda mais
+    pub fn confirmed_state(&self) -> Option<&NetState> {
+        let confirmed = self.confirmed_frame();
+        if confirmed == self.frame {
+            return self.state();
+        }
+        self.snapshots
+            .iter()
+            .find(|(frame, _)| *frame == confirmed)
+            .map(|(_, state)| state)
+    }
+
+    pub fn rollbacks(&self) -> u32 {
+        self.rollbacks
+    }
+
+    // Avança um passo com a entrada local. Devolve falso se a partida ainda não começou ou se
+    // este lado está `MAX_PREDICTION` passos à frente do adversário e precisa esperar por ele
+    pub fn advance(&mut self, input: NetInput) -> bool {
+        self.poll();
+
+        let waiting = self.frame >= self.remote.len() as u32 + MAX_PREDICTION;
+        if self.state.is_none() || waiting {
+            self.send();
+            return false;
+        }
+
+        self.local.push(input);
+        self.simulate(self.frame);
+        self.frame += 1;
+        self.send();
+        true
+    }
+
+    // Lê os pacotes recebidos e corrige a simulação se alguma previsão estava errada
+    pub fn poll(&mut self) {
+        let mut buffer = [0; 512];
+        let mut mispredicted: Option<u32> = None;
+
+        while let Ok((size, from)) = self.socket.recv_from(&mut buffer) {
+            if from != self.peer {
+                continue;
+            }
+            let Some(packet) = Packet::decode(&buffer[..size]) else {
+                continue;
+            };
+
+            self.peer_ack = self.peer_ack.max(packet.ack);
+            if self.state.is_none() && self.player == 1 {
+                self.seed = packet.seed;
+                self.state = Some(NetState::new(packet.seed, &self.setup));
+            }
+
+            // Guarda só as entradas que continuam a sequência já conhecida
+            for (offset, input) in packet.inputs.into_iter().enumerate() {
+                let frame = packet.first_frame + offset as u32;
+                if frame != self.remote.len() as u32 {
+                    continue;
+                }
+                self.remote.push(input);
+                let predicted = self.used_remote.get(frame as usize);
+                if predicted.is_some_and(|predicted| *predicted != input) {
+                    mispredicted = Some(mispredicted.map_or(frame, |first| first.min(frame)));
+                }
+            }
+        }
+
+        if let Some(frame) = mispredicted {
+            self.rollback(frame);
+        }
+
+        // Estados anteriores ao último passo confirmado não serão mais necessários
+        let confirmed = self.confirmed_frame();
+        self.snapshots.retain(|(frame, _)| *frame >= confirmed);
+    }
+
+    // Volta ao estado do início de `frame` e simula de novo até o passo atual
+    fn rollback(&mut self, frame: u32) {
+        let Some(index) = self.snapshots.iter().position(|(saved, _)| *saved == frame) else {
+            return;
+        };
+        self.state = Some(self.snapshots[index].1.clone());
+        self.snapshots.truncate(index);
+        self.rollbacks += 1;
+        for frame in frame..self.frame {
+            self.simulate(frame);
+        }
+    }
+
+    // Guarda o estado e simula um passo, usando a entrada confirmada do adversário ou a prevista
+    fn simulate(&mut self, frame: u32) {
+        let Some(state) = self.state.as_mut() else {
+            return;
+        };
+        self.snapshots.push((frame, state.clone()));
+
+        let remote = self
+            .remote
+            .get(frame as usize)
+            .or(self.remote.last())
+            .copied()
+            .unwrap_or_default();
+        if let Some(used) = self.used_remote.get_mut(frame as usize) {
+            *used = remote;
+        } else {
+            self.used_remote.push(remote);
+        }
+
+        let local = self.local[frame as usize];
+        let inputs = if self.player == 0 {
+            [local, remote]
+        } else {
+            [remote, local]
+        };
+        state.advance(inputs, &self.setup);
+    }
+
+    // Envia as entradas locais que o adversário ainda não confirmou
+    fn send(&self) {
+        let first_frame = self.peer_ack.min(self.local.len() as u32);
+        let inputs = self.local[first_frame as usize..]
+            .iter()
+            .take(MAX_PACKET_INPUTS)
+            .copied()
+            .collect();
+        let packet = Packet {
+            seed: self.seed,
+            ack: self.remote.len() as u32,
+            first_frame,
+            inputs,
+        };
+        // Pacotes perdidos são reenviados no próximo passo
+        let _ = self.socket.send_to(&packet.encode(), self.peer);
+    }
+}
+
+// Pacote trocado a cada passo: a semente, quantas entradas do outro lado já foram recebidas e
+// as entradas locais a partir de `first_frame`
+struct Packet {
+    seed: u64,
+    ack: u32,
+    first_frame: u32,
+    inputs: Vec<NetInput>,
+}
+
+impl Packet {
+    fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(19 + self.inputs.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&self.seed.to_le_bytes());
+        bytes.extend_from_slice(&self.ack.to_le_bytes());
+        bytes.extend_from_slice(&self.first_frame.to_le_bytes());
+        bytes.push(self.inputs.len() as u8);
+        bytes.extend(self.inputs.iter().map(|input| input.0 as u8));
+        bytes
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        let (magic, rest) = bytes.split_first_chunk::<2>()?;
+        let (seed, rest) = rest.split_first_chunk::<8>()?;
+        let (ack, rest) = rest.split_first_chunk::<4>()?;
+        let (first_frame, rest) = rest.split_first_chunk::<4>()?;
+        let (count, inputs) = rest.split_first()?;
+        if magic != MAGIC || inputs.len() != *count as usize {
+            return None;
+        }
+        Some(Self {
+            seed: u64::from_le_bytes(*seed),
+            ack: u32::from_le_bytes(*ack),
+            first_frame: u32::from_le_bytes(*first_frame),
+            inputs: inputs.iter().map(|byte| NetInput(*byte as i8)).collect(),
+        })
+    }
+}
+
+// Raquetes consultadas pela sessão online, separadas da bola
+type NetPaddle = (With<Paddle>, Without<Ball>);
+
+// Sistema que avança a sessão online a cada passo fixo com a entrada do jogador local e copia o
+// estado simulado para as entidades e o placar
+pub fn advance_net_session(
+    mut session: ResMut<NetSession>,
+    actions: Res<PlayerActions>,
+    mut ball: Query<(&mut Position, &mut Velocity), With<Ball>>,
+    mut paddles: Query<(&mut Position, &mut Velocity, Has<Player>), NetPaddle>,
+    mut score: ResMut<Score>,
+) {
+    // Os dois jogadores usam os controles do primeiro jogador do `InputMap`
+    session.advance(NetInput::from_movement(actions.get(0).movement));
+
+    let Some(state) = session.state() else {
+        return;
+    };
+    if let Ok((mut position, mut velocity)) = ball.get_single_mut() {
+        position.0 = state.ball;
+        velocity.0 = state.ball_velocity;
+    }
+    for (mut position, mut velocity, right) in &mut paddles {
+        let index = if right { 0 } else { 1 };
+        position.0 = state.paddles[index];
+        velocity.0 = Vec2::new(0., state.paddle_movement[index]);
+    }
+    score.right = state.score[0];
+    score.left = state.score[1];
+    score.right_sets = state.sets[0];
+    score.left_sets = state.sets[1];
+}
+
+// Sistema que encerra a partida online quando a sessão confirma o vencedor. Espera a
+// confirmação para que uma correção não desfaça um fim de jogo já anunciado
+pub fn end_net_match(
+    session: Res<NetSession>,
+    mut match_won: EventWriter<MatchWon>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut ended: Local<bool>,
+) {
+    let winner = session.confirmed_state().and_then(|state| state.winner);
+    if let (Some(winner), false) = (winner, *ended) {
+        *ended = true;
+        match_won.send(MatchWon(winner));
+        next_state.set(GameState::GameOver);
+    }
+}
+
+// Sistema que marca a partida como online: a sessão conduz a partida dos dois lados, então o
+// saque e a pausa deixam de trocar o estado local
+pub fn mark_online_match(mut commands: Commands) {
+    commands.init_resource::<OnlineMatch>();
+}
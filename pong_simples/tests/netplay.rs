@@ -0,0 +1,236 @@
+use std::{
+    net::{SocketAddr, UdpSocket},
+    thread,
+    time::Duration,
+};
+
+use bevy::prelude::*;
+use pong::{NetInput, NetSession, NetSetup, NetState, PongSimplesPlugin, Position, MAX_PREDICTION};
+use pong_core::{step, GameMode, GameState, HeadlessPlugin, MatchRules, Player, Score, Scorer};
+
+// Reserva uma porta livre no localhost
+fn free_port() -> SocketAddr {
+    UdpSocket::bind("127.0.0.1:0")
+        .and_then(|socket| socket.local_addr())
+        .unwrap()
+}
+
+// Duas sessões ligadas uma à outra, como duas instâncias do jogo na mesma máquina
+fn connected_sessions(seed: u64) -> (NetSession, NetSession) {
+    let (first, second) = (free_port(), free_port());
+    let host = NetSession::new(first, second, 0, seed, NetSetup::default()).unwrap();
+    let guest = NetSession::new(second, first, 1, 0, NetSetup::default()).unwrap();
+    (host, guest)
+}
+
+// Entradas roteirizadas de cada jogador, que mudam com frequência para forçar previsões erradas
+fn host_input(frame: u32) -> NetInput {
+    NetInput(if (frame / 15).is_multiple_of(2) {
+        127
+    } else {
+        -127
+    })
+}
+
+fn guest_input(frame: u32) -> NetInput {
+    NetInput([-100, 0, 100][(frame as usize / 20) % 3])
+}
+
+// Lê os pacotes até que as duas sessões conheçam todas as entradas dos passos já simulados
+fn settle(host: &mut NetSession, guest: &mut NetSession) {
+    for _ in 0..1000 {
+        host.poll();
+        guest.poll();
+        if host.confirmed_frame() == host.frame() && guest.confirmed_frame() == guest.frame() {
+            return;
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+    panic!("as sessões não receberam todas as entradas");
+}
+
+#[test]
+fn input_is_quantized_to_a_byte() {
+    assert_eq!(NetInput::from_movement(1.), NetInput(127));
+    assert_eq!(NetInput::from_movement(-2.), NetInput(-127));
+    assert_eq!(NetInput::from_movement(0.), NetInput(0));
+    assert_eq!(NetInput(127).movement(), 1.);
+}
+
+#[test]
+fn guest_waits_for_the_host_seed_and_host_waits_for_the_guest() {
+    let (mut host, mut guest) = connected_sessions(42);
+
+    assert!(!guest.advance(NetInput::default()));
+    assert!(guest.state().is_none());
+
+    // Sem notícias do convidado, o anfitrião só prevê alguns passos e depois espera
+    for _ in 0..MAX_PREDICTION + 4 {
+        host.advance(NetInput::default());
+    }
+    assert_eq!(host.frame(), MAX_PREDICTION);
+
+    thread::sleep(Duration::from_millis(10));
+    assert!(guest.advance(NetInput::default()));
+    assert!(guest.state().is_some());
+}
+
+#[test]
+fn both_instances_reach_the_same_state_as_a_local_simulation() {
+    const FRAMES: u32 = 600;
+    let (mut host, mut guest) = connected_sessions(7);
+
+    // Cada lado avança alguns passos seguidos antes do outro, como em máquinas com atraso, e
+    // quem sai na frente se alterna para que os dois precisem prever
+    let mut round = 0u32;
+    while host.frame() < FRAMES || guest.frame() < FRAMES {
+        let (first, second) = if round.is_multiple_of(2) {
+            (&mut host, &mut guest)
+        } else {
+            (&mut guest, &mut host)
+        };
+        for session in [first, second] {
+            for _ in 0..5 {
+                if session.frame() < FRAMES {
+                    let frame = session.frame();
+                    let input = if session.player() == 0 {
+                        host_input(frame)
+                    } else {
+                        guest_input(frame)
+                    };
+                    session.advance(input);
+                }
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+        round += 1;
+    }
+    settle(&mut host, &mut guest);
+
+    let setup = NetSetup::default();
+    let mut reference = NetState::new(7, &setup);
+    for frame in 0..FRAMES {
+        reference.advance([host_input(frame), guest_input(frame)], &setup);
+    }
+
+    assert!(host.rollbacks() > 0 && guest.rollbacks() > 0);
+    for session in [&host, &guest] {
+        let state = session.state().unwrap();
+        assert_eq!(state.checksum(), reference.checksum());
+        assert_eq!(state.score, reference.score);
+    }
+    assert_ne!(reference.ball, Vec2::ZERO, "a bola deveria estar em jogo");
+}
+
+#[test]
+fn online_app_is_driven_by_the_session() {
+    let mut app = App::new();
+    let session = NetSession::new(free_port(), free_port(), 0, 3, NetSetup::default()).unwrap();
+    app.add_plugins(HeadlessPlugin::default())
+        .insert_state(GameState::Playing)
+        .insert_resource(GameMode::TwoPlayers)
+        .insert_resource(session)
+        .add_plugins(PongSimplesPlugin);
+    // Sem o outro jogador a sessão para depois de alguns passos previstos
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(KeyCode::ArrowUp);
+    step(&mut app, 2 * MAX_PREDICTION);
+
+    let session = app.world().resource::<NetSession>();
+    assert_eq!(session.frame(), MAX_PREDICTION);
+    let expected = session.state().unwrap().paddles[0];
+    let mut paddle = app.world_mut().query_filtered::<&Position, With<Player>>();
+    let position = paddle.single(app.world()).0;
+    assert_eq!(position, expected);
+    assert!(position.y > 0., "a raquete deveria subir com a seta");
+}
+
+// Regras curtas para que as partidas online terminem em poucos pontos
+fn short_match() -> NetSetup {
+    NetSetup {
+        rules: MatchRules {
+            points_to_win: 1,
+            win_by_two: false,
+            best_of: 3,
+            ..default()
+        },
+        ..default()
+    }
+}
+
+#[test]
+fn state_applies_the_match_rules() {
+    let setup = short_match();
+    let mut state = NetState::new(5, &setup);
+
+    // As raquetes ficam nas pontas, então a bola passa por elas
+    let inputs = [NetInput(127), NetInput(-127)];
+    for _ in 0..64 * 120 {
+        state.advance(inputs, &setup);
+        if state.winner.is_some() {
+            break;
+        }
+    }
+
+    let winner = state.winner.expect("a partida deveria ter acabado");
+    let side = if winner == Scorer::Right { 0 } else { 1 };
+    assert_eq!(state.sets[side], 2);
+    assert!(state.sets[1 - side] < 2);
+    assert_eq!(state.score[side], 1); // Pontos do último set
+
+    // Depois do fim nada mais muda
+    let checksum = state.checksum();
+    for _ in 0..100 {
+        state.advance(inputs, &setup);
+    }
+    assert_eq!(state.checksum(), checksum);
+    assert_eq!(state.ball_velocity, Vec2::ZERO);
+}
+
+#[test]
+fn online_match_ignores_pause_and_ends_when_a_side_wins() {
+    let (host_address, guest_address) = (free_port(), free_port());
+    let host = NetSession::new(host_address, guest_address, 0, 5, short_match()).unwrap();
+    let mut guest = NetSession::new(guest_address, host_address, 1, 0, short_match()).unwrap();
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugin::default())
+        .insert_state(GameState::Playing)
+        .insert_resource(GameMode::TwoPlayers)
+        .insert_resource(host)
+        .add_plugins(PongSimplesPlugin);
+
+    // A pausa não congela só um dos lados
+    let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keyboard.press(KeyCode::Escape);
+    step(&mut app, 2);
+    assert_eq!(
+        *app.world().resource::<State<GameState>>().get(),
+        GameState::Playing
+    );
+    let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keyboard.release(KeyCode::Escape);
+    keyboard.clear();
+
+    // O convidado desce até a ponta e o anfitrião fica parado no centro
+    for _ in 0..64 * 120 {
+        step(&mut app, 1);
+        guest.advance(NetInput(-127));
+        let state = app.world().resource::<State<GameState>>().get();
+        if *state == GameState::GameOver {
+            break;
+        }
+    }
+
+    assert_eq!(
+        *app.world().resource::<State<GameState>>().get(),
+        GameState::GameOver
+    );
+    let score = app.world().resource::<Score>();
+    assert_eq!(score.left_sets.max(score.right_sets), 2);
+    let session = app.world().resource::<NetSession>();
+    assert_eq!(
+        session.confirmed_state().unwrap().sets,
+        guest.state().unwrap().sets
+    );
+}
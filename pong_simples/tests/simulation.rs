@@ -0,0 +1,641 @@
+use std::time::Duration;
+
+use bevy::{
+    math::bounding::{Aabb2d, BoundingCircle},
+    prelude::*,
+    time::TimeUpdateStrategy,
+};
+use pong::{
+    collide_with_side, sweep_circle_aabb, Collision, Gutter, PongSimplesPlugin, Position, Shape,
+    Velocity,
+};
+use pong_core::{
+    start_replay, step, Ai, AiSettings, Arena, Ball, BallHit, Difficulty, GameConfig, GameMode,
+    GameRng, GameState, HeadlessPlugin, HitKind, Paddle, Player, Replay, ReplayPlayer,
+    ReplayRecorder, Score, BALL_SPEED, MAX_SERVE_ANGLE, MIN_SERVE_ANGLE, PADDLE_HEIGHT,
+    WALL_THICKNESS,
+};
+
+// Cria o jogo sem janela e executa o primeiro passo para criar as entidades
+fn headless_app() -> App {
+    headless_app_with(|_| {})
+}
+
+// Como `headless_app`, permitindo inserir recursos antes dos plugins do jogo
+fn headless_app_with(configure: impl FnOnce(&mut App)) -> App {
+    let mut app = App::new();
+    // Começa jogando, sem passar pelo menu e pelo saque
+    app.add_plugins(HeadlessPlugin::default())
+        .insert_state(GameState::Playing);
+    configure(&mut app);
+    app.add_plugins(PongSimplesPlugin);
+    step(&mut app, 1);
+    app
+}
+
+// Coloca a bola na posição e velocidade indicadas
+fn place_ball(app: &mut App, position: Vec2, velocity: Vec2) {
+    let mut ball = app
+        .world_mut()
+        .query_filtered::<(&mut Position, &mut Velocity), With<Ball>>();
+    let (mut ball_position, mut ball_velocity) = ball.single_mut(app.world_mut());
+    ball_position.0 = position;
+    ball_velocity.0 = velocity;
+}
+
+// Aperta e solta uma tecla, como em um quadro real
+fn tap(app: &mut App, key: KeyCode) {
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(key);
+    step(app, 1);
+    let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keyboard.release(key);
+    keyboard.clear();
+    step(app, 1); // Aplica a troca de estado
+}
+
+fn state(app: &App) -> GameState {
+    *app.world().resource::<State<GameState>>().get()
+}
+
+fn ball_state(app: &mut App) -> (Vec2, Vec2) {
+    let mut ball = app
+        .world_mut()
+        .query_filtered::<(&Position, &Velocity), With<Ball>>();
+    let (position, velocity) = ball.single(app.world());
+    (position.0, velocity.0)
+}
+
+// Toques da bola enviados desde o início do teste
+#[derive(Resource, Default)]
+struct Hits(Vec<HitKind>);
+
+fn collect_hits(mut events: EventReader<BallHit>, mut hits: ResMut<Hits>) {
+    hits.0.extend(events.read().map(|hit| hit.kind));
+}
+
+// Como `headless_app`, guardando os toques da bola em `Hits`
+fn app_collecting_hits() -> App {
+    headless_app_with(|app| {
+        app.init_resource::<Hits>()
+            .add_systems(Update, collect_hits);
+    })
+}
+
+#[test]
+fn collide_with_side_reports_each_face() {
+    let wall = Aabb2d::new(Vec2::ZERO, Vec2::new(10., 10.));
+
+    let cases = [
+        (Vec2::new(-12., 0.), Collision::Left),
+        (Vec2::new(12., 0.), Collision::Right),
+        (Vec2::new(0., 12.), Collision::Top),
+        (Vec2::new(0., -12.), Collision::Bottom),
+    ];
+
+    for (center, expected) in cases {
+        let ball = BoundingCircle::new(center, 5.);
+        assert_eq!(collide_with_side(ball, wall), Some(expected), "{center}");
+    }
+}
+
+#[test]
+fn collide_with_side_ignores_distant_ball() {
+    let wall = Aabb2d::new(Vec2::ZERO, Vec2::new(10., 10.));
+    let ball = BoundingCircle::new(Vec2::new(30., 30.), 5.);
+
+    assert_eq!(collide_with_side(ball, wall), None);
+}
+
+#[test]
+fn sweep_finds_first_contact_along_the_path() {
+    let wall = Aabb2d::new(Vec2::ZERO, Vec2::new(5., 20.));
+
+    // Parte de x = -50 e anda 100 unidades; toca a caixa expandida (x = -10) na fração 0,4
+    let (time_of_impact, side) =
+        sweep_circle_aabb(Vec2::new(-50., 0.), 5., Vec2::new(100., 0.), wall).unwrap();
+    assert!((time_of_impact - 0.4).abs() < 1e-6, "{time_of_impact}");
+    assert_eq!(side, Collision::Left);
+
+    let (_, side) = sweep_circle_aabb(Vec2::new(0., 60.), 5., Vec2::new(0., -100.), wall).unwrap();
+    assert_eq!(side, Collision::Top);
+}
+
+#[test]
+fn sweep_ignores_paths_that_miss_or_stop_short() {
+    let wall = Aabb2d::new(Vec2::ZERO, Vec2::new(5., 20.));
+
+    let short = sweep_circle_aabb(Vec2::new(-50., 0.), 5., Vec2::new(30., 0.), wall);
+    assert_eq!(short, None);
+    let away = sweep_circle_aabb(Vec2::new(-50., 0.), 5., Vec2::new(-100., 0.), wall);
+    assert_eq!(away, None);
+    let above = sweep_circle_aabb(Vec2::new(-50., 40.), 5., Vec2::new(100., 0.), wall);
+    assert_eq!(above, None);
+}
+
+#[test]
+fn ball_moves_by_its_velocity_each_fixed_step() {
+    let mut app = headless_app();
+    place_ball(&mut app, Vec2::ZERO, Vec2::new(-1., 1.));
+
+    step(&mut app, 10);
+
+    // Cada passo fixo dura 1/64 s e a bola anda `BALL_SPEED` unidades por segundo
+    let expected = Vec2::new(-1., 1.) * BALL_SPEED * 10. / 64.;
+    let (position, velocity) = ball_state(&mut app);
+    assert!(position.distance(expected) < 1e-3, "{position}");
+    assert_eq!(velocity, Vec2::new(-1., 1.));
+}
+
+#[test]
+fn ball_speed_changes_apply_during_the_match() {
+    let mut app = headless_app();
+    place_ball(&mut app, Vec2::ZERO, Vec2::new(1., 0.));
+
+    // Como ao recarregar o arquivo de configuração com o jogo aberto
+    app.world_mut().resource_mut::<GameConfig>().ball_speed = 2. * BALL_SPEED;
+    step(&mut app, 10);
+
+    let (position, _) = ball_state(&mut app);
+    let expected = 2. * BALL_SPEED * 10. / 64.;
+    assert!((position.x - expected).abs() < 1e-3, "{position}");
+}
+
+#[test]
+fn ball_bounces_off_gutters() {
+    let mut app = headless_app();
+    let arena = *app.world().resource::<Arena>();
+    let gutter_edge = arena.half_size().y - WALL_THICKNESS;
+    place_ball(
+        &mut app,
+        Vec2::new(0., gutter_edge - 10.),
+        Vec2::new(0., 1.),
+    );
+
+    step(&mut app, 20);
+
+    let (position, velocity) = ball_state(&mut app);
+    assert!(velocity.y < 0., "a bola deveria descer após a barreira");
+    assert!(position.y < gutter_edge);
+}
+
+#[test]
+fn ball_bounces_off_player_paddle() {
+    let mut app = headless_app();
+    let mut paddle = app.world_mut().query_filtered::<&Position, With<Player>>();
+    let paddle_x = paddle.single(app.world()).0.x;
+    place_ball(&mut app, Vec2::new(paddle_x - 20., 0.), Vec2::new(1., 0.));
+
+    step(&mut app, 20);
+
+    let (_, velocity) = ball_state(&mut app);
+    assert_eq!(velocity, Vec2::new(-1., 0.));
+}
+
+#[test]
+fn bounces_send_one_hit_per_contact() {
+    let mut app = app_collecting_hits();
+    let arena = *app.world().resource::<Arena>();
+    let gutter_edge = arena.half_size().y - WALL_THICKNESS;
+    place_ball(
+        &mut app,
+        Vec2::new(0., gutter_edge - 10.),
+        Vec2::new(0., 1.),
+    );
+    step(&mut app, 20);
+    assert_eq!(app.world().resource::<Hits>().0, [HitKind::Wall]);
+
+    let mut paddle = app.world_mut().query_filtered::<&Position, With<Player>>();
+    let paddle_x = paddle.single(app.world()).0.x;
+    place_ball(&mut app, Vec2::new(paddle_x - 20., 0.), Vec2::new(1., 0.));
+    step(&mut app, 20);
+    assert_eq!(
+        app.world().resource::<Hits>().0,
+        [HitKind::Wall, HitKind::Paddle]
+    );
+}
+
+#[test]
+fn hard_ai_returns_the_ball() {
+    let mut app = headless_app_with(|app| {
+        app.insert_resource(AiSettings::from(Difficulty::Hard));
+    });
+    place_ball(&mut app, Vec2::new(-600., 0.), Vec2::new(-1., 1.));
+
+    // Tempo suficiente para a bola chegar à raquete da IA e voltar um pouco
+    step(&mut app, 400);
+
+    let (_, velocity) = ball_state(&mut app);
+    assert!(velocity.x > 0., "a IA deveria rebater a bola");
+    let score = app.world().resource::<Score>();
+    assert_eq!((score.left, score.right), (0, 0));
+}
+
+#[test]
+fn paddle_hit_above_centre_sends_ball_upwards() {
+    let mut app = headless_app();
+    let mut paddle = app.world_mut().query_filtered::<&Position, With<Player>>();
+    let paddle_x = paddle.single(app.world()).0.x;
+    place_ball(&mut app, Vec2::new(paddle_x - 20., 15.), Vec2::new(1., 0.));
+
+    step(&mut app, 20);
+
+    let (_, velocity) = ball_state(&mut app);
+    assert!(velocity.x < 0., "a bola deveria voltar após a raquete");
+    assert!(
+        velocity.y > 0.,
+        "a bola deveria subir após tocar acima do centro"
+    );
+    assert!((velocity.length() - 1.).abs() < 1e-4); // O módulo da velocidade se mantém
+}
+
+#[test]
+fn ball_at_max_speed_does_not_tunnel_through_paddle() {
+    let mut app = headless_app();
+    let max_speed = app.world().resource::<GameConfig>().max_ball_speed;
+    let mut paddle = app.world_mut().query_filtered::<&Position, With<Player>>();
+    let paddle_x = paddle.single(app.world()).0.x;
+    // A cada passo a bola anda mais do que a largura da raquete
+    place_ball(
+        &mut app,
+        Vec2::new(paddle_x - 100., 0.),
+        Vec2::new(max_speed / BALL_SPEED, 0.),
+    );
+
+    step(&mut app, 3);
+
+    let (position, velocity) = ball_state(&mut app);
+    assert!(velocity.x < 0., "a bola deveria voltar após a raquete");
+    assert!(position.x < paddle_x, "{position}");
+    let score = app.world().resource::<Score>();
+    assert_eq!((score.left, score.right), (0, 0));
+}
+
+#[test]
+fn scoring_recentres_ball_and_updates_score() {
+    let mut app = headless_app();
+    let arena = *app.world().resource::<Arena>();
+    place_ball(
+        &mut app,
+        Vec2::new(arena.half_size().x + 1., 0.),
+        Vec2::new(1., 0.),
+    );
+
+    step(&mut app, 2);
+
+    // A bola espera no centro pelo saque de quem sofreu o ponto
+    let (position, _) = ball_state(&mut app);
+    assert_eq!(position, Vec2::ZERO);
+    assert_eq!(state(&app), GameState::Serving);
+    let score = app.world().resource::<Score>();
+    assert_eq!((score.left, score.right), (1, 0));
+
+    tap(&mut app, KeyCode::Space);
+    let (_, velocity) = ball_state(&mut app);
+    assert!(velocity.x < 0., "a direita saca em direção à esquerda");
+    assert_serve_angle(velocity);
+}
+
+#[test]
+fn player_scores_when_ball_leaves_left_side() {
+    let mut app = headless_app();
+    let arena = *app.world().resource::<Arena>();
+    place_ball(
+        &mut app,
+        Vec2::new(-arena.half_size().x - 1., 0.),
+        Vec2::new(-1., 0.),
+    );
+
+    step(&mut app, 2);
+
+    let (position, _) = ball_state(&mut app);
+    assert_eq!(position, Vec2::ZERO);
+    let score = app.world().resource::<Score>();
+    assert_eq!((score.left, score.right), (0, 1));
+
+    tap(&mut app, KeyCode::Space);
+    let (_, velocity) = ball_state(&mut app);
+    assert!(velocity.x > 0., "a esquerda saca em direção à direita");
+    assert_serve_angle(velocity);
+}
+
+// Confere que o saque sorteado fica na faixa de ângulos e mantém a rapidez do saque diagonal
+fn assert_serve_angle(velocity: Vec2) {
+    let angle = (velocity.y / velocity.x).abs().atan();
+    assert!(
+        (MIN_SERVE_ANGLE - 1e-4..=MAX_SERVE_ANGLE + 1e-4).contains(&angle),
+        "{angle}"
+    );
+    assert!((velocity.length() - std::f32::consts::SQRT_2).abs() < 1e-4);
+}
+
+#[test]
+fn ball_is_served_when_countdown_expires() {
+    let mut app = headless_app_with(|app| {
+        app.insert_state(GameState::Serving);
+    });
+    let delay = app.world().resource::<GameConfig>().serve_delay;
+
+    // Pouco antes do fim da contagem a bola continua parada no centro
+    let steps = (delay * 64.) as u32;
+    step(&mut app, steps - 4);
+    assert_eq!(state(&app), GameState::Serving);
+    assert_eq!(ball_state(&mut app), (Vec2::ZERO, Vec2::ZERO));
+
+    step(&mut app, 6); // Termina a contagem e aplica a troca de estado
+
+    assert_eq!(state(&app), GameState::Playing);
+    let (_, velocity) = ball_state(&mut app);
+    assert!(
+        velocity.x < 0.,
+        "a direita saca primeiro, em direção à esquerda"
+    );
+    assert_serve_angle(velocity);
+}
+
+#[test]
+fn same_seed_serves_the_same_way() {
+    let serve = |seed| {
+        let mut app = headless_app_with(|app| {
+            app.insert_state(GameState::Serving)
+                .insert_resource(GameRng::new(seed));
+        });
+        tap(&mut app, KeyCode::Space);
+        ball_state(&mut app).1
+    };
+
+    assert_eq!(serve(42), serve(42));
+}
+
+#[test]
+fn paddles_stay_inside_the_gutters() {
+    let mut app = headless_app();
+    // Sem o `InputPlugin` a tecla permanece pressionada em todos os passos
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(KeyCode::ArrowUp);
+    step(&mut app, 500);
+
+    let arena = *app.world().resource::<Arena>();
+    let mut gutters = app.world_mut().query_filtered::<&Position, With<Gutter>>();
+    assert_eq!(gutters.iter(app.world()).count(), 2);
+
+    let mut paddles = app.world_mut().query_filtered::<&Position, With<Paddle>>();
+    for position in paddles.iter(app.world()) {
+        assert!(position.0.y.abs() < arena.half_size().y - WALL_THICKNESS);
+    }
+}
+
+#[test]
+fn reloaded_sizes_wait_for_the_next_match() {
+    let mut app = headless_app();
+    // Como ao recarregar o arquivo de configuração com o jogo aberto
+    let mut config = app.world_mut().resource_mut::<GameConfig>();
+    config.paddle_height = 10.;
+    config.wall_thickness = 0.;
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(KeyCode::ArrowUp);
+    step(&mut app, 500);
+
+    // As raquetes continuam parando nas barreiras desta partida
+    let arena = *app.world().resource::<Arena>();
+    let mut paddles = app.world_mut().query_filtered::<&Position, With<Paddle>>();
+    for position in paddles.iter(app.world()) {
+        assert!(position.0.y.abs() < arena.half_size().y - WALL_THICKNESS - PADDLE_HEIGHT / 2.);
+    }
+
+    // A próxima partida é criada com as novas medidas
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::MainMenu);
+    step(&mut app, 1);
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Serving);
+    step(&mut app, 1);
+    let mut gutters = app.world_mut().query_filtered::<&Shape, With<Gutter>>();
+    for shape in gutters.iter(app.world()) {
+        assert_eq!(shape.0.y, 0.);
+    }
+}
+
+#[test]
+fn two_players_mode_gives_the_left_paddle_to_a_second_player() {
+    let mut app = headless_app_with(|app| {
+        app.insert_resource(GameMode::TwoPlayers);
+    });
+
+    let mut ai = app.world_mut().query_filtered::<(), With<Ai>>();
+    assert_eq!(ai.iter(app.world()).count(), 0);
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(KeyCode::KeyW);
+    step(&mut app, 10);
+
+    let mut paddles = app
+        .world_mut()
+        .query_filtered::<&Position, (With<Paddle>, Without<Player>)>();
+    let left = paddles.single(app.world());
+    assert!(left.0.x < 0. && left.0.y > 0., "{}", left.0);
+}
+
+#[test]
+fn ball_stays_still_while_paused() {
+    let mut app = headless_app();
+    place_ball(&mut app, Vec2::ZERO, Vec2::new(-1., 1.));
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Paused);
+
+    step(&mut app, 10);
+
+    let (position, _) = ball_state(&mut app);
+    assert_eq!(position, Vec2::ZERO);
+}
+
+#[test]
+fn pausing_during_the_countdown_still_serves_the_ball() {
+    let mut app = headless_app_with(|app| {
+        app.insert_state(GameState::Serving);
+    });
+
+    tap(&mut app, KeyCode::Escape);
+    tap(&mut app, KeyCode::Escape);
+    assert_eq!(state(&app), GameState::Serving);
+    step(&mut app, 2000);
+
+    let (position, velocity) = ball_state(&mut app);
+    assert_ne!(velocity, Vec2::ZERO, "a bola deveria ter sido sacada");
+    let score = app.world().resource::<Score>();
+    assert!(position != Vec2::ZERO || score.left + score.right > 0);
+}
+
+#[test]
+fn scored_ball_waits_still_for_the_serve() {
+    let mut app = headless_app();
+    let arena = *app.world().resource::<Arena>();
+    place_ball(
+        &mut app,
+        Vec2::new(arena.half_size().x + 1., 0.),
+        Vec2::new(1., 0.),
+    );
+    step(&mut app, 2);
+
+    tap(&mut app, KeyCode::Escape);
+    tap(&mut app, KeyCode::Escape);
+    step(&mut app, 10);
+
+    assert_eq!(state(&app), GameState::Serving);
+    assert_eq!(ball_state(&mut app), (Vec2::ZERO, Vec2::ZERO));
+}
+
+#[test]
+fn movement_does_not_depend_on_frame_rate() {
+    // Um segundo simulado com quadros de 30 Hz e de 120 Hz
+    let mut positions = Vec::new();
+    for frames_per_second in [30, 120] {
+        let mut app = headless_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1. / frames_per_second as f64,
+        )));
+        place_ball(&mut app, Vec2::ZERO, Vec2::new(-1., 1.));
+        step(&mut app, frames_per_second);
+        positions.push(ball_state(&mut app).0);
+    }
+
+    // As posições diferem no máximo por um passo fixo ainda não executado
+    let fixed_step = Vec2::new(-1., 1.).length() * BALL_SPEED / 64.;
+    assert!(
+        positions[0].distance(positions[1]) <= fixed_step + 1e-3,
+        "{positions:?}"
+    );
+    assert!(
+        (positions[0].x + BALL_SPEED).abs() <= fixed_step + 1e-3,
+        "{positions:?}"
+    );
+}
+
+// Joga uma partida curta contra a IA: saca, segura a seta para cima e depois para baixo
+fn play_recorded_match(app: &mut App) {
+    tap(app, KeyCode::Space);
+    for (key, steps) in [(KeyCode::ArrowUp, 60), (KeyCode::ArrowDown, 120)] {
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(key);
+        step(app, steps);
+        let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keyboard.release(key);
+        keyboard.clear();
+    }
+    step(app, 200);
+}
+
+fn recording_app(seed: u64) -> App {
+    headless_app_with(|app| {
+        app.insert_state(GameState::Serving)
+            .insert_resource(GameRng::new(seed))
+            .insert_resource(ReplayRecorder::new("unused.ron"));
+    })
+}
+
+fn replaying_app(replay: Replay) -> App {
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugin::default())
+        .insert_state(GameState::Serving);
+    start_replay(&mut app, replay.clone());
+    app.add_plugins(PongSimplesPlugin);
+    // Repete todos os quadros gravados, inclusive o de criação feito por `headless_app_with`
+    step(&mut app, replay.frame_count() as u32);
+    app
+}
+
+#[test]
+fn replay_repeats_the_recorded_match() {
+    let mut recorded = recording_app(7);
+    play_recorded_match(&mut recorded);
+    let replay = recorded.world().resource::<ReplayRecorder>().replay.clone();
+    assert_eq!(replay.seed, 7);
+
+    let mut replayed = replaying_app(replay.clone());
+
+    let player = replayed.world().resource::<ReplayPlayer>();
+    assert!(player.finished());
+    assert_eq!(player.diverged(), None);
+    assert_eq!(ball_state(&mut replayed), ball_state(&mut recorded));
+    let paddle = |app: &mut App| {
+        let mut query = app.world_mut().query_filtered::<&Position, With<Player>>();
+        query.single(app.world()).0
+    };
+    assert_eq!(paddle(&mut replayed), paddle(&mut recorded));
+}
+
+#[test]
+fn replay_does_not_depend_on_the_recorded_frame_rate() {
+    // Grava a 60 quadros por segundo, fora do ritmo dos passos fixos; a repetição avança só
+    // os passos gravados em cada quadro
+    let mut recorded = headless_app_with(|app| {
+        app.insert_state(GameState::Serving)
+            .insert_resource(GameRng::new(7))
+            .insert_resource(ReplayRecorder::new("unused.ron"))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+                1. / 60.,
+            )));
+    });
+    play_recorded_match(&mut recorded);
+    let replay = recorded.world().resource::<ReplayRecorder>().replay.clone();
+    assert!(replay.frames.iter().any(|(_, ticks)| *ticks != 1));
+
+    let mut replayed = replaying_app(replay);
+
+    assert_eq!(replayed.world().resource::<ReplayPlayer>().diverged(), None);
+    assert_eq!(ball_state(&mut replayed), ball_state(&mut recorded));
+}
+
+#[test]
+fn replay_starts_from_the_recorded_difficulty() {
+    let mut recorded = headless_app_with(|app| {
+        app.insert_state(GameState::MainMenu)
+            .insert_resource(Difficulty::Easy)
+            .insert_resource(AiSettings::from(Difficulty::Easy))
+            .insert_resource(ReplayRecorder::new("unused.ron"));
+    });
+    tap(&mut recorded, KeyCode::ArrowUp); // Fácil -> Normal no menu
+    play_recorded_match(&mut recorded);
+    let replay = recorded.world().resource::<ReplayRecorder>().replay.clone();
+    assert_eq!(replay.difficulty, Difficulty::Easy);
+
+    let mut replayed = App::new();
+    replayed
+        .add_plugins(HeadlessPlugin::default())
+        .insert_state(GameState::MainMenu);
+    start_replay(&mut replayed, replay.clone());
+    replayed.add_plugins(PongSimplesPlugin);
+    step(&mut replayed, replay.frame_count() as u32);
+
+    assert_eq!(
+        *replayed.world().resource::<Difficulty>(),
+        Difficulty::Normal
+    );
+    assert_eq!(
+        *replayed.world().resource::<AiSettings>(),
+        AiSettings::from(Difficulty::Normal)
+    );
+    assert_eq!(replayed.world().resource::<ReplayPlayer>().diverged(), None);
+}
+
+#[test]
+fn replay_reports_the_first_divergent_tick() {
+    let mut recorded = recording_app(7);
+    play_recorded_match(&mut recorded);
+    let mut replay = recorded.world().resource::<ReplayRecorder>().replay.clone();
+    // Outra semente muda o ângulo do saque, então a bola se afasta logo depois do saque
+    replay.seed = 8;
+
+    let replayed = replaying_app(replay);
+
+    let diverged = replayed.world().resource::<ReplayPlayer>().diverged();
+    assert!(diverged.is_some_and(|tick| tick > 0), "{diverged:?}");
+}
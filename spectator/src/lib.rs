@@ -0,0 +1,143 @@
+use bevy::{prelude::*, render::camera::ScalingMode};
+use pong_core::{
+    fit_camera_to_window, spawn_camera, spawn_scoreboard, update_scoreboard, Arena, Ball, Paddle,
+    Player, Score, SpectatorClient, SpectatorFrame,
+};
+
+// Raquetes desenhadas, separadas da bola para posicionar as duas no mesmo sistema
+type PaddleView = (&'static mut Transform, &'static mut Visibility, Has<Player>);
+
+// Recurso com o último quadro recebido e há quanto tempo ele chegou
+#[derive(Resource, Default)]
+pub struct LatestFrame {
+    pub frame: Option<SpectatorFrame>, // Último quadro recebido
+    pub age: f32,                      // Segundos desde que ele chegou
+}
+
+// Sistema que cria a bola e as raquetes com tamanho unitário; o tamanho real vem em cada quadro
+pub fn spawn_view(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.spawn((
+        Ball,
+        Mesh2d(meshes.add(Circle::new(1.))),
+        MeshMaterial2d(materials.add(Color::srgb(1., 0., 0.))), // Vermelho, como no jogo
+        Visibility::Hidden,                                     // Escondida até o primeiro quadro
+    ));
+
+    // Mesmas cores do jogo: verde à direita e azul à esquerda
+    let paddle_mesh = meshes.add(Rectangle::new(1., 1.));
+    for (right, color) in [
+        (true, Color::srgb(0., 1., 0.)),
+        (false, Color::srgb(0., 0., 1.)),
+    ] {
+        let mut paddle = commands.spawn((
+            Paddle,
+            Mesh2d(paddle_mesh.clone()),
+            MeshMaterial2d(materials.add(color)),
+            Visibility::Hidden,
+        ));
+        if right {
+            paddle.insert(Player);
+        }
+    }
+}
+
+// Sistema que lê os quadros do servidor, guarda o mais recente e atualiza o placar e a arena
+pub fn receive_frames(
+    mut client: ResMut<SpectatorClient>,
+    mut latest: ResMut<LatestFrame>,
+    mut score: ResMut<Score>,
+    mut arena: ResMut<Arena>,
+    mut projections: Query<&mut OrthographicProjection>,
+    time: Res<Time>,
+) {
+    latest.age += time.delta_secs();
+
+    let Some(frame) = client.poll() else {
+        return;
+    };
+
+    score.left = frame.left;
+    score.right = frame.right;
+    score.left_sets = frame.left_sets;
+    score.right_sets = frame.right_sets;
+
+    // Acompanha o tamanho da arena da versão transmitida
+    if arena.size != frame.arena {
+        *arena = Arena::new(frame.arena.x, frame.arena.y);
+        for mut projection in &mut projections {
+            projection.scaling_mode = ScalingMode::Fixed {
+                width: arena.width(),
+                height: arena.height(),
+            };
+        }
+    }
+
+    latest.frame = Some(frame);
+    latest.age = 0.;
+}
+
+// Sistema que posiciona a bola e as raquetes conforme o último quadro. Entre um quadro e outro a
+// bola segue em linha reta com a velocidade recebida, para o movimento não ficar aos saltos
+pub fn apply_frame(
+    latest: Res<LatestFrame>,
+    mut ball: Query<(&mut Transform, &mut Visibility), With<Ball>>,
+    mut paddles: Query<PaddleView, (With<Paddle>, Without<Ball>)>,
+) {
+    let Some(frame) = &latest.frame else {
+        return;
+    };
+
+    if let Ok((mut transform, mut visibility)) = ball.get_single_mut() {
+        let position = frame.ball + frame.ball_velocity * latest.age;
+        *transform = Transform::from_translation(position.extend(0.))
+            .with_scale(Vec3::splat(frame.ball_radius));
+        *visibility = Visibility::Inherited;
+    }
+
+    for (mut transform, mut visibility, right) in &mut paddles {
+        let position = if right {
+            frame.right_paddle
+        } else {
+            frame.left_paddle
+        };
+        *transform = Transform::from_translation(position.extend(0.))
+            .with_scale(frame.paddle_size.extend(1.));
+        *visibility = Visibility::Inherited;
+    }
+}
+
+// Sistema que avisa uma vez quando a transmissão termina
+pub fn report_disconnection(client: Res<SpectatorClient>, mut reported: Local<bool>) {
+    if !client.connected() && !*reported {
+        info!("A transmissão terminou");
+        *reported = true;
+    }
+}
+
+// Plugin do espectador: desenha a partida recebida de um `SpectatorServer`. O `SpectatorClient`
+// é inserido antes do plugin, já conectado. Funciona tanto com o `DefaultPlugins` quanto com o
+// `HeadlessPlugin`.
+pub struct SpectatorPlugin;
+
+impl Plugin for SpectatorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Arena>()
+            .init_resource::<Score>()
+            .init_resource::<LatestFrame>()
+            .add_systems(Startup, (spawn_camera, spawn_view, spawn_scoreboard))
+            .add_systems(
+                Update,
+                (
+                    receive_frames,
+                    apply_frame.after(receive_frames),
+                    update_scoreboard.after(receive_frames),
+                    fit_camera_to_window.after(receive_frames),
+                    report_disconnection,
+                ),
+            );
+    }
+}
@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+use pong_core::SpectatorClient;
+use spectator::SpectatorPlugin;
+
+// Assiste a uma partida transmitida por um jogo iniciado com `--spectators PORTA`
+fn main() {
+    // `--connect endereço` escolhe o servidor; por padrão o da própria máquina
+    let address = std::env::args()
+        .skip_while(|arg| arg != "--connect")
+        .nth(1)
+        .unwrap_or_else(|| "127.0.0.1:7100".to_string());
+
+    let client = match SpectatorClient::connect(&address) {
+        Ok(client) => client,
+        Err(error) => {
+            eprintln!("Não foi possível conectar a {address}: {error}");
+            std::process::exit(1);
+        }
+    };
+
+    App::new()
+        .insert_resource(client)
+        .add_plugins((DefaultPlugins, SpectatorPlugin))
+        .run();
+}
@@ -0,0 +1,97 @@
+use std::{
+    io::Write,
+    net::{TcpListener, TcpStream},
+    thread,
+    time::Duration,
+};
+
+use bevy::prelude::*;
+use pong_core::{step, Ball, HeadlessPlugin, Player, Score, SpectatorClient, SpectatorFrame};
+use spectator::{LatestFrame, SpectatorPlugin};
+
+// Espectador sem janela ligado a um servidor falso, que envia os quadros escritos pelo teste
+fn spectator_app() -> (App, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let client = SpectatorClient::connect(listener.local_addr().unwrap()).unwrap();
+    let (server, _) = listener.accept().unwrap();
+
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugin::default())
+        .insert_resource(client)
+        .add_plugins(SpectatorPlugin);
+    step(&mut app, 1);
+    (app, server)
+}
+
+fn send(server: &mut TcpStream, frame: &SpectatorFrame) {
+    server.write_all(&frame.encode()).unwrap();
+    thread::sleep(Duration::from_millis(5));
+}
+
+fn frame() -> SpectatorFrame {
+    SpectatorFrame {
+        arena: Vec2::new(1280., 720.),
+        ball: Vec2::new(30., 40.),
+        ball_velocity: Vec2::new(64., 0.),
+        ball_radius: 5.,
+        right_paddle: Vec2::new(590., 25.),
+        left_paddle: Vec2::new(-590., -10.),
+        paddle_size: Vec2::new(10., 50.),
+        right: 2,
+        left_sets: 1,
+        ..default()
+    }
+}
+
+fn ball_transform(app: &mut App) -> Transform {
+    let mut ball = app.world_mut().query_filtered::<&Transform, With<Ball>>();
+    *ball.single(app.world())
+}
+
+#[test]
+fn received_frame_places_ball_paddles_and_score() {
+    let (mut app, mut server) = spectator_app();
+    send(&mut server, &frame());
+    step(&mut app, 1);
+
+    let ball = ball_transform(&mut app);
+    assert_eq!(ball.translation.truncate(), Vec2::new(30., 40.));
+    assert_eq!(ball.scale, Vec3::splat(5.));
+    let mut paddle = app.world_mut().query_filtered::<&Transform, With<Player>>();
+    assert_eq!(
+        paddle.single(app.world()).translation.truncate(),
+        Vec2::new(590., 25.)
+    );
+    let score = app.world().resource::<Score>();
+    assert_eq!((score.left, score.right, score.left_sets), (0, 2, 1));
+}
+
+#[test]
+fn ball_moves_between_frames_with_its_velocity() {
+    let (mut app, mut server) = spectator_app();
+    send(&mut server, &frame());
+    step(&mut app, 1);
+
+    // Sem novos quadros, meio segundo depois a bola andou metade da velocidade
+    step(&mut app, 32);
+
+    let age = app.world().resource::<LatestFrame>().age;
+    assert!((age - 0.5).abs() < 1e-3, "{age}");
+    let x = ball_transform(&mut app).translation.x;
+    assert!((x - (30. + 64. * age)).abs() < 1e-3, "{x}");
+}
+
+#[test]
+fn only_the_latest_frame_is_used() {
+    let (mut app, mut server) = spectator_app();
+    let mut old = frame();
+    old.ball = Vec2::new(-100., 0.);
+    send(&mut server, &old);
+    send(&mut server, &frame());
+    step(&mut app, 1);
+
+    assert_eq!(
+        ball_transform(&mut app).translation.truncate(),
+        Vec2::new(30., 40.)
+    );
+}